@@ -1,5 +1,5 @@
 use clap::{App, AppSettings, Arg, SubCommand};
-use validators::{is_valid_i32, is_valid_path};
+use validators::{is_valid_i64, is_valid_path};
 
 pub fn app<'a, 'b>() -> App<'a, 'b> {
     App::new("Advent of Code Day 2")
@@ -20,11 +20,19 @@ pub fn app<'a, 'b>() -> App<'a, 'b> {
                         .long("alarm")
                         .help("Runs the program in the '1202 program alarm' state")
                         .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name("patch")
+                        .short("p")
+                        .long("patch")
+                        .help("The path to a patch file (lines of 'location = values') to apply before running")
+                        .takes_value(true)
+                        .validator_os(is_valid_path),
                 ),
         )
         .subcommand(
             SubCommand::with_name("reverse")
-                .about("Finds the noun and verb for a given output and Intcode program")
+                .about("Finds the noun and verb for a given output and Intcode program, by solving for them symbolically")
                 .arg(
                     Arg::with_name("input")
                         .help("The path to the input file")
@@ -37,7 +45,14 @@ pub fn app<'a, 'b>() -> App<'a, 'b> {
                         .help("The output that the program will search for")
                         .takes_value(true)
                         .required(true)
-                        .validator(is_valid_i32),
+                        .validator(is_valid_i64),
+                )
+                .arg(
+                    Arg::with_name("unbounded")
+                        .short("u")
+                        .long("unbounded")
+                        .help("Allows nouns and verbs outside of the range 0 to 99")
+                        .takes_value(false),
                 ),
         )
         .setting(AppSettings::SubcommandRequiredElseHelp)
@@ -1,7 +1,15 @@
 mod app;
 use app::*;
 use intcode::*;
-use std::fs::read_to_string;
+use std::{
+    fs::read_to_string,
+    io::{Stdin, Stdout},
+};
+
+// helper function to check a noun and verb by running the program with them
+fn gives(pc: &mut IntcodeComputer<Stdin, Stdout>, noun: i64, verb: i64, wanted: i64) -> bool {
+    pc.set(noun, verb).is_ok() && matches!(pc.run(), Ok(report) if report.result == wanted)
+}
 
 fn main() {
     let matches = app().get_matches();
@@ -9,53 +17,100 @@ fn main() {
     let subcommand_matches = matches.subcommand().1.unwrap();
 
     // open the input file, parse it and get the memory
-    let memory: Vec<i32> = read_to_string(subcommand_matches.value_of_os("input").unwrap())
+    let memory: Vec<i64> = read_to_string(subcommand_matches.value_of_os("input").unwrap())
         .expect("Failed to read the file")
         .split(',')
-        .map(|s| s.parse::<i32>().expect("Malformed input"))
+        .map(|s| s.parse::<i64>().expect("Malformed input"))
         .collect();
-    let mut pc = IntcodeComputer::with_stdio(memory);
+    let mut pc = IntcodeComputer::with_stdio(memory.clone());
 
     match subcommand_name {
         "run" => {
             // optionally restore the gravity assist program to the "1202 program alarm" state
             if subcommand_matches.is_present("alarm") {
-                pc.set(12, 2);
+                if let Err(err) = pc.set(12, 2) {
+                    return eprintln!("Failed to set the noun and verb: {}", err);
+                }
             }
 
-            // run the program
-            let output = pc.run_once();
+            // optionally apply a patch file on top
+            if let Some(path) = subcommand_matches.value_of_os("patch") {
+                let patch: Patch<i64> = match read_to_string(path)
+                    .expect("Failed to read the patch file")
+                    .parse()
+                {
+                    Ok(patch) => patch,
+                    Err(err) => return eprintln!("Malformed patch file: {}", err),
+                };
+                if let Err(err) = pc.apply_patch(&patch) {
+                    return eprintln!("Failed to apply the patch: {}", err);
+                }
+            }
 
-            println!("Memory[0]: {}", output);
+            // run the program
+            match pc.run_once() {
+                Ok(report) => println!("Memory[0]: {}", report.result),
+                Err(err) => eprintln!("The program failed: {}", err),
+            }
         }
         "reverse" => {
             // get the wanted output
-            let wanted_output: i32 = subcommand_matches
+            let wanted_output: i64 = subcommand_matches
                 .value_of("output")
                 .unwrap()
                 .parse()
                 .unwrap();
 
-            let (noun, verb) = {
-                let mut noun = -1;
-                let mut verb = -1;
-
-                'outer: for i in 0..100 {
-                    for j in 0..100 {
-                        pc.set(i, j);
-                        let answer = pc.run();
-                        if answer == wanted_output {
-                            noun = i;
-                            verb = j;
-                            break 'outer;
-                        }
+            // make the noun and the verb symbolic, and solve for the wanted output
+            let mut computer = pc.to_symbolic();
+            let noun = computer.symbolize(1, "noun");
+            let verb = computer.symbolize(2, "verb");
+            if !subcommand_matches.is_present("unbounded") {
+                for symbol in &[&noun, &verb] {
+                    computer.assume(Constraint::greater_or_equal(*symbol, 0));
+                    computer.assume(Constraint::less_or_equal(*symbol, 99));
+                }
+            }
+            let solution = computer.find(|path| {
+                let output = path.memory(0)?;
+                Some(vec![Constraint::equal(output, wanted_output)])
+            });
+            let solution = solution
+                // a symbol that the output does not depend on can take any value
+                .map(|(_, solution)| {
+                    (
+                        solution.value(&noun).unwrap_or(0),
+                        solution.value(&verb).unwrap_or(0),
+                    )
+                })
+                // the solution may rest on approximated symbols (see `Solution::is_exact`),
+                // so it is only trusted once the program gives the wanted output with it
+                .filter(|&(noun, verb)| gives(&mut pc, noun, verb, wanted_output));
+            let (noun, verb) = match solution {
+                Some(solution) => solution,
+                // the symbolic execution gives up on some programs (e.g. when the noun
+                // is used as an instruction), and its solution may not hold, so fall
+                // back to searching the usual range
+                None => {
+                    let mut search = Search::new(memory);
+                    search.vary(SearchVariable::Memory(1), 0..=99);
+                    search.vary(SearchVariable::Memory(2), 0..=99);
+                    search.set_limits(Limits {
+                        instructions: Some(1_000_000),
+                        ..Limits::default()
+                    });
+                    let matches = search
+                        .run(|candidate| match candidate.memory.get(0) {
+                            Ok(output) if *output == wanted_output => Some(()),
+                            _ => None,
+                        })
+                        .expect("The nouns and verbs fit in a word");
+                    match matches.first() {
+                        Some(found) => (found.values[0], found.values[1]),
+                        None => panic!("Could not find a noun an verb for the given output!"),
                     }
                 }
-                (noun, verb)
             };
-            if noun == -1 || verb == -1 {
-                panic!("Could not find a noun an verb for the given output!");
-            }
             println!(
                 "Noun: {}, Verb: {}, Product: {}",
                 noun,
@@ -60,8 +60,8 @@ fn main() {
 
     // check for intersections
     let mut intersections: Vec<Intersection> = vec![];
-    for (idx1, line1) in (&lines[0]).iter().enumerate() {
-        for (idx2, line2) in (&lines[1]).iter().enumerate() {
+    for (idx1, line1) in lines[0].iter().enumerate() {
+        for (idx2, line2) in lines[1].iter().enumerate() {
             if let Some(point) = line1.intersects(line2) {
                 if (idx1, idx2) != (0, 0) {
                     intersections.push(Intersection {
@@ -86,7 +86,7 @@ fn main() {
                     intersection.point.x.abs() + intersection.point.y.abs(),
                 )
             })
-            .fold((Point { x: 0, y: 0 }, std::i32::MAX), |lowest, curr| {
+            .fold((Point { x: 0, y: 0 }, i32::MAX), |lowest, curr| {
                 if curr.1 < lowest.1 {
                     curr
                 } else {
@@ -106,21 +106,21 @@ fn main() {
             .map(|intersection| {
                 // check the number of steps
                 let end_line1 = Line::new(intersection.line1.beginning, intersection.point);
-                let steps1 = (&lines[0])
+                let steps1 = lines[0]
                     .iter()
                     .take(intersection.line1_idx)
                     .chain(once(&end_line1))
                     .fold(0, |ctr, line| ctr + line.len());
 
                 let end_line2 = Line::new(intersection.line2.beginning, intersection.point);
-                let steps2 = (&lines[1])
+                let steps2 = lines[1]
                     .iter()
                     .take(intersection.line2_idx)
                     .chain(once(&end_line2))
                     .fold(0, |ctr, line| ctr + line.len());
                 (intersection.point, steps1 + steps2)
             })
-            .fold((Point { x: 0, y: 0 }, std::i32::MAX), |lowest, curr| {
+            .fold((Point { x: 0, y: 0 }, i32::MAX), |lowest, curr| {
                 if curr.1 < lowest.1 {
                     curr
                 } else {
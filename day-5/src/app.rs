@@ -12,18 +12,27 @@ pub fn app<'a, 'b>() -> App<'a, 'b> {
                 .required(true)
                 .validator_os(is_valid_path),
         )
+        .arg(
+            Arg::with_name("ascii")
+                .short("a")
+                .long("ascii")
+                .help("Reads the input as lines of text and prints the output as characters.")
+                .takes_value(false),
+        )
         .setting(AppSettings::ArgRequiredElseHelp)
 }
 
 #[derive(Debug)]
 pub struct Opt {
     pub path: PathBuf,
+    pub ascii: bool,
 }
 
 impl From<ArgMatches<'_>> for Opt {
     fn from(matches: ArgMatches) -> Self {
         Self {
             path: matches.value_of_os("input").unwrap().into(),
+            ascii: matches.is_present("ascii"),
         }
     }
 }
@@ -5,12 +5,20 @@ use std::fs::read_to_string;
 fn main() {
     // load the program
     let opt: Opt = app().get_matches().into();
-    let memory: Vec<i32> = read_to_string(opt.path)
+    let memory: Vec<i64> = read_to_string(opt.path)
         .expect("Failed to read the file")
         .trim()
         .split(',')
-        .map(|s| s.parse::<i32>().expect("Malformed input"))
+        .map(|s| s.parse::<i64>().expect("Malformed input"))
         .collect();
-    let pc = IntcodeComputer::with_stdio(memory);
-    pc.run_once();
+
+    // run it, talking either in numbers or in text
+    let result = if opt.ascii {
+        IntcodeComputer::new(memory, AsciiInput::stdin(), AsciiOutput::stdout()).run_once()
+    } else {
+        IntcodeComputer::with_stdio(memory).run_once()
+    };
+    if let Err(err) = result {
+        eprintln!("The program failed: {}", err);
+    }
 }
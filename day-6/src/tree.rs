@@ -73,10 +73,7 @@ impl TreeBuilder {
             .iter()
             .map(|(name, parent, depth)| TreeNode {
                 depth: *depth,
-                parent: match parent {
-                    Some(name) => Some(*node_indices.get(name).unwrap()),
-                    None => None,
-                },
+                parent: parent.as_ref().map(|name| *node_indices.get(name).unwrap()),
                 children_indices: match self.node_children.get(name) {
                     Some(children) => children
                         .iter()
@@ -112,7 +109,7 @@ impl Tree {
     pub fn distance(&self, src: &str, dst: &str) -> Option<i32> {
         let idx1 = self.indices.get(src);
         let idx2 = self.indices.get(dst);
-        if idx1 == None || idx2 == None {
+        if idx1.is_none() || idx2.is_none() {
             return None;
         }
 
@@ -150,7 +147,7 @@ impl Tree {
 
     fn traverse_impl<F: FnMut(&TreeNode)>(&self, root: usize, f: Rc<RefCell<F>>) {
         let node = self.nodes.get(root).unwrap();
-        (&mut *f.borrow_mut())(node);
+        (*f.borrow_mut())(node);
 
         for child in node.children_indices.iter() {
             self.traverse_impl(*child, f.clone());
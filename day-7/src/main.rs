@@ -1,47 +1,7 @@
 mod app;
 use app::*;
 use intcode::*;
-use std::{
-    fs::read_to_string,
-    sync::{
-        mpsc::{channel, Receiver, Sender},
-        Arc, Mutex,
-    },
-    thread,
-};
-
-#[derive(Debug, Clone)]
-struct MultiReciever {
-    receiver: Arc<Mutex<Receiver<i32>>>,
-}
-
-impl Input for MultiReciever {
-    fn get(&mut self) -> Result<i32, StreamError> {
-        self.receiver.lock().unwrap().get()
-    }
-}
-
-impl From<Receiver<i32>> for MultiReciever {
-    fn from(receiver: Receiver<i32>) -> Self {
-        Self {
-            receiver: Arc::new(Mutex::new(receiver)),
-        }
-    }
-}
-
-struct Stream {
-    receiver: MultiReciever,
-    sender: Sender<i32>,
-}
-
-impl From<(Sender<i32>, Receiver<i32>)> for Stream {
-    fn from((sender, receiver): (Sender<i32>, Receiver<i32>)) -> Self {
-        Self {
-            receiver: receiver.into(),
-            sender,
-        }
-    }
-}
+use std::{collections::VecDeque, fs::read_to_string};
 
 fn generate_phase_settings(feedback: bool) -> [[i32; 5]; 120] {
     let offset = if feedback { 5 } else { 0 };
@@ -84,10 +44,8 @@ fn generate_phase_settings(feedback: bool) -> [[i32; 5]; 120] {
 }
 
 fn regular_run(memory: Vec<i32>) {
-    // prepare the io stream and amplifier
-    let mut input: Stream = channel().into();
-    let mut output: Stream = channel().into();
-    let mut ampilfier = IntcodeComputer::new(memory, input.receiver, output.sender);
+    // prepare the amplifier, which only gets the inputs of each run
+    let mut ampilfier = IntcodeComputer::new(memory, VecDeque::new(), |_| {});
 
     // generate all the possible phase settings
     let phase_settings = generate_phase_settings(false);
@@ -95,24 +53,15 @@ fn regular_run(memory: Vec<i32>) {
     let mut highest_thrust_value = 0;
     let mut highest_thrust_idx = 0;
     for (idx, phase_setting) in phase_settings.iter().enumerate() {
-        // setup the input
-        input.sender.push(phase_setting[0]);
-        input.sender.push(0);
-
-        // run the amplifiers
-        ampilfier.run(); // run the first one
-        for setting in &phase_setting[1..5] {
-            let output = output.receiver.get().unwrap();
-
-            // setup the input
-            input.sender.push(*setting);
-            input.sender.push(output);
-
-            // run the amplifier
-            ampilfier.run();
+        // run the amplifiers, feeding the output of each one to the next
+        let mut value = 0;
+        for setting in phase_setting {
+            let outputs = ampilfier
+                .run_with_inputs(&[*setting, value])
+                .expect("The amplifier failed");
+            value = outputs[0];
         }
 
-        let value = output.receiver.get().unwrap();
         if value > highest_thrust_value {
             highest_thrust_value = value;
             highest_thrust_idx = idx
@@ -132,47 +81,33 @@ fn feedback_run(memory: Vec<i32>) {
     let mut highest_thrust_idx = 0;
 
     for (idx, phase_setting) in phase_settings.iter().enumerate() {
-        // create the streams that will communicate between the amplifiers
-        let mut streams: Vec<Stream> = Vec::new();
-        for _ in 0..5 {
-            streams.push(channel().into());
+        // create the amplifiers and set their phase settings
+        let mut network = Network::new();
+        for setting in phase_setting {
+            let amplifier = network.add_machine(memory.clone());
+            network.push_input(amplifier, *setting);
         }
-    
-        // create the amplifiers and wire them up
-        let mut amplifiers = Vec::new();
-        for i in 0..5 {
-            let input_idx = ((i as i32 - 1 + 5) % 5) as usize;
-            let input = streams[input_idx].receiver.clone();
-            let output = streams[i].sender.clone();
-
-            // set the phase settings
-            streams[input_idx].sender.push(phase_setting[i]);
 
-            amplifiers.push(IntcodeComputer::new(memory.clone(), input, output));
-        }
-    
-        // send the first signal (0) to amplifier A
-        streams[4].sender.push(0);
-    
-        // run the amplifiers
-        let mut join_handles = Vec::new();
-        for mut amplifier in amplifiers.drain(..) {
-            join_handles.push(thread::spawn(move || amplifier.run()));
+        // wire them up in a loop, each one feeding the next
+        for i in 0..5 {
+            network.connect(i, (i + 1) % 5);
         }
-    
-        // wait for the amplifiers to finish
-        for handle in join_handles.drain(..) {
-            handle.join().unwrap();
+
+        // send the first signal (0) to amplifier A, and run the amplifiers
+        network.push_input(0, 0);
+        match network.run() {
+            NetworkHalt::Halted => {}
+            halt => panic!("The amplifiers failed: {:?}", halt),
         }
 
         // get the thrust
-        let thrust = streams[4].receiver.get().unwrap();
+        let thrust = *network.outputs(4).last().expect("No thrust");
         if thrust > highest_thrust_value {
             highest_thrust_value = thrust;
             highest_thrust_idx = idx
         }
     }
-    
+
     println!(
         "Highest thrust: {}, achieved with phase settings: {:?}",
         highest_thrust_value, phase_settings[highest_thrust_idx]
@@ -0,0 +1,367 @@
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use intcode::{SearchMode, SearchVariable};
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+    path::PathBuf,
+};
+use validators::is_valid_path;
+
+/// The default number of instructions each candidate of a search may execute.
+const DEFAULT_SEARCH_INSTRUCTIONS: &str = "1000000";
+
+pub fn app<'a, 'b>() -> App<'a, 'b> {
+    App::new("Intcode")
+        .about("Tools for working with Intcode programs")
+        .subcommand(
+            SubCommand::with_name("disassemble")
+                .about("Prints a symbolic listing of an Intcode program")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("assemble")
+                .about("Assembles an Intcode assembly source into a comma-separated program")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the assembly source.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("analyze")
+                .about("Statically analyzes an Intcode program, without running it")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                )
+                .arg(
+                    Arg::with_name("dot")
+                        .help("Prints the control-flow graph in the Graphviz DOT format.")
+                        .long("dot"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("decompile")
+                .about("Decompiles an Intcode program into structured pseudocode")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                )
+                .arg(
+                    Arg::with_name("parameter")
+                        .help("The location of a memory cell that is set before the program runs (e.g. the noun).")
+                        .long("parameter")
+                        .short("p")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .validator(is_valid_location),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("search")
+                .about("Searches the values of memory cells and inputs for which a program gives the wanted result")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                )
+                .arg(
+                    Arg::with_name("memory")
+                        .help("A memory cell and its values, e.g. 1=0..100 or 1=0..=99 (or a single value).")
+                        .long("memory")
+                        .short("m")
+                        .value_name("LOC=RANGE")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .validator(|value| parse_variable(&value).map(|_| ())),
+                )
+                .arg(
+                    Arg::with_name("program-input")
+                        .help("An input position (0 is the first input) and its values, e.g. 0=0..5.")
+                        .long("input")
+                        .short("i")
+                        .value_name("POS=RANGE")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .validator(|value| parse_variable(&value).map(|_| ())),
+                )
+                .arg(
+                    Arg::with_name("target")
+                        .help("The result to check: memory[LOC], output[N] or output (the last output).")
+                        .long("target")
+                        .short("t")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|value| parse_target(&value).map(|_| ())),
+                )
+                .arg(
+                    Arg::with_name("equals")
+                        .help("Only matches the candidates whose target equals the given value.")
+                        .long("equals")
+                        .short("e")
+                        .takes_value(true)
+                        .allow_hyphen_values(true)
+                        .validator(|value| {
+                            value
+                                .parse::<i64>()
+                                .map(|_| ())
+                                .map_err(|_| format!("{} is not a valid value", value))
+                        }),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .help("Prints all of the matches, instead of the first one.")
+                        .long("all"),
+                )
+                .arg(
+                    Arg::with_name("min")
+                        .help("Prints the match with the smallest target.")
+                        .long("min"),
+                )
+                .arg(
+                    Arg::with_name("max")
+                        .help("Prints the match with the largest target.")
+                        .long("max"),
+                )
+                .group(ArgGroup::with_name("mode").args(&["all", "min", "max"]))
+                .arg(
+                    Arg::with_name("instructions")
+                        .help("The maximal number of instructions each candidate executes.")
+                        .long("instructions")
+                        .takes_value(true)
+                        .default_value(DEFAULT_SEARCH_INSTRUCTIONS)
+                        .validator(is_valid_count),
+                )
+                .arg(
+                    Arg::with_name("threads")
+                        .help("The number of threads (default: the number of cores).")
+                        .long("threads")
+                        .takes_value(true)
+                        .validator(is_valid_count),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("network")
+                .about("Runs a network of machines, wired as described by a topology file, and prints its result")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program that each machine runs.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                )
+                .arg(
+                    Arg::with_name("topology")
+                        .help("The topology file, with 'machine', 'input', 'link' and 'result' statements.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("debug")
+                .about("Runs an Intcode program in an interactive debugger")
+                .arg(
+                    Arg::with_name("input")
+                        .help("The input file containing the program.")
+                        .takes_value(true)
+                        .required(true)
+                        .validator_os(is_valid_path),
+                ),
+        )
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+}
+
+fn is_valid_location(value: String) -> Result<(), String> {
+    value
+        .parse::<usize>()
+        .map(|_| ())
+        .map_err(|_| format!("{} is not a valid memory location", value))
+}
+
+fn is_valid_count(value: String) -> Result<(), String> {
+    match value.parse::<u64>() {
+        Ok(count) if count > 0 => Ok(()),
+        _ => Err(format!("{} is not a positive number", value)),
+    }
+}
+
+/// Parses a location (or position) and its range of values, e.g. `1=0..100`.
+fn parse_variable(value: &str) -> Result<(usize, RangeInclusive<i64>), String> {
+    let invalid = || format!("{} is not of the form LOC=RANGE (e.g. 1=0..100)", value);
+    let mut parts = value.splitn(2, '=');
+    let location = parts
+        .next()
+        .and_then(|location| location.trim().parse::<usize>().ok())
+        .ok_or_else(invalid)?;
+    let range = parts.next().ok_or_else(invalid)?.trim();
+    let parse = |bound: &str| bound.trim().parse::<i64>().map_err(|_| invalid());
+    let range = if let Some(idx) = range.find("..=") {
+        parse(&range[..idx])?..=parse(&range[idx + 3..])?
+    } else if let Some(idx) = range.find("..") {
+        let end = parse(&range[idx + 2..])?;
+        parse(&range[..idx])?..=end.checked_sub(1).ok_or_else(invalid)?
+    } else {
+        let value = parse(range)?;
+        value..=value
+    };
+    Ok((location, range))
+}
+
+/// The result of a program that a search checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// The memory cell at the given location.
+    Memory(usize),
+    /// The output at the given index, or the last output.
+    Output(Option<usize>),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Target::Memory(address) => write!(f, "memory[{}]", address),
+            Target::Output(Some(idx)) => write!(f, "output[{}]", idx),
+            Target::Output(None) => write!(f, "output"),
+        }
+    }
+}
+
+/// Parses a target, e.g. `memory[0]`.
+fn parse_target(value: &str) -> Result<Target, String> {
+    let invalid = || format!("{} is not one of memory[LOC], output[N] or output", value);
+    let value = value.trim();
+    if value == "output" {
+        return Ok(Target::Output(None));
+    }
+    let open = value.find('[').ok_or_else(invalid)?;
+    if !value.ends_with(']') {
+        return Err(invalid());
+    }
+    let idx = value[open + 1..value.len() - 1]
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| invalid())?;
+    match &value[..open] {
+        "memory" => Ok(Target::Memory(idx)),
+        "output" => Ok(Target::Output(Some(idx))),
+        _ => Err(invalid()),
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Disassemble,
+    Assemble,
+    Analyze {
+        dot: bool,
+    },
+    Decompile {
+        parameters: Vec<usize>,
+    },
+    Search {
+        variables: Vec<(SearchVariable, RangeInclusive<i64>)>,
+        target: Target,
+        equals: Option<i64>,
+        mode: SearchMode,
+        instructions: u64,
+        threads: Option<usize>,
+    },
+    Network {
+        topology: PathBuf,
+    },
+    Debug,
+}
+
+#[derive(Debug)]
+pub struct Opt {
+    pub path: PathBuf,
+    pub command: Command,
+}
+
+impl From<ArgMatches<'_>> for Opt {
+    fn from(matches: ArgMatches) -> Self {
+        let (name, subcommand_matches) = matches.subcommand();
+        let subcommand_matches = subcommand_matches.unwrap();
+        Self {
+            path: subcommand_matches.value_of_os("input").unwrap().into(),
+            command: match name {
+                "disassemble" => Command::Disassemble,
+                "assemble" => Command::Assemble,
+                "analyze" => Command::Analyze {
+                    dot: subcommand_matches.is_present("dot"),
+                },
+                "decompile" => Command::Decompile {
+                    parameters: subcommand_matches
+                        .values_of("parameter")
+                        .map(|values| values.map(|value| value.parse().unwrap()).collect())
+                        .unwrap_or_default(),
+                },
+                "search" => {
+                    let variables = |name, variable: fn(usize) -> SearchVariable| {
+                        subcommand_matches
+                            .values_of(name)
+                            .into_iter()
+                            .flatten()
+                            .map(move |value| {
+                                let (location, range) = parse_variable(value).unwrap();
+                                (variable(location), range)
+                            })
+                    };
+                    let mode = if subcommand_matches.is_present("all") {
+                        SearchMode::All
+                    } else if subcommand_matches.is_present("min") {
+                        SearchMode::Min
+                    } else if subcommand_matches.is_present("max") {
+                        SearchMode::Max
+                    } else {
+                        SearchMode::First
+                    };
+                    Command::Search {
+                        variables: variables("memory", SearchVariable::Memory)
+                            .chain(variables("program-input", SearchVariable::Input))
+                            .collect(),
+                        target: parse_target(subcommand_matches.value_of("target").unwrap())
+                            .unwrap(),
+                        equals: subcommand_matches
+                            .value_of("equals")
+                            .map(|value| value.parse().unwrap()),
+                        mode,
+                        instructions: subcommand_matches
+                            .value_of("instructions")
+                            .unwrap()
+                            .parse()
+                            .unwrap(),
+                        threads: subcommand_matches
+                            .value_of("threads")
+                            .map(|value| value.parse().unwrap()),
+                    }
+                }
+                "network" => Command::Network {
+                    topology: subcommand_matches.value_of_os("topology").unwrap().into(),
+                },
+                "debug" => Command::Debug,
+                _ => unreachable!(),
+            },
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use intcode::*;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    io::{self, BufRead, Stdin, Stdout, Write},
+    ops::Range,
+};
+
+/// The number of executed instructions shown before the current one.
+const HISTORY_SIZE: usize = 3;
+
+/// The number of instructions shown starting at the current one.
+const LISTING_SIZE: usize = 6;
+
+/// The maximal number of memory locations shown at once.
+const MEMORY_VIEW_LIMIT: usize = 1024;
+
+const HELP: &str = "\
+Commands:
+  step [n]           (s)  executes the next n instructions (default: 1)
+  continue           (c)  runs until a breakpoint, input is needed or the program ends
+  break <addr>       (b)  sets a breakpoint at the given address
+  delete <addr>      (d)  removes the breakpoint at the given address
+  breakpoints        (bl) lists the breakpoints
+  watch <addr> [n]   (w)  pauses when one of n locations starting at the given address is written (default: 1)
+  rwatch <addr> [n]  (rw) pauses when one of n locations starting at the given address is read (default: 1)
+  vwatch <addr> <v>  (vw) pauses when v is written to the given address
+  unwatch            (uw) removes all the watchpoints
+  registers          (r)  shows the program counter and the relative base
+  list               (l)  shows the instructions around the program counter
+  memory <addr> [n]  (x)  shows n memory locations starting at the given address (default: 8)
+  poke <addr> <v>    (p)  sets the memory location at the given address to v
+  input <v>...       (i)  queues values for the program's input instructions
+  output             (o)  shows the values the program has outputted
+  help               (h)  shows this message
+  quit               (q)  exits the debugger";
+
+/// The state of the program being debugged.
+#[derive(PartialEq)]
+enum State {
+    Running,
+    Halted,
+    Faulted,
+}
+
+/// An interactive debugger for Intcode programs.
+pub struct Debugger {
+    computer: IntcodeComputer<Stdin, Stdout>,
+    breakpoints: BTreeSet<usize>,
+    outputs: Vec<i64>,
+    history: VecDeque<usize>,
+    state: State,
+}
+
+impl Debugger {
+    pub fn new(memory: Vec<i64>) -> Self {
+        Self {
+            computer: IntcodeComputer::with_stdio(memory),
+            breakpoints: BTreeSet::new(),
+            outputs: Vec::new(),
+            history: VecDeque::new(),
+            state: State::Running,
+        }
+    }
+
+    /// Runs the debugger's REPL until the user quits or the input ends.
+    pub fn run(&mut self) {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        self.list();
+        loop {
+            print!("(debug) ");
+            io::stdout().flush().unwrap();
+            let line = match lines.next() {
+                Some(Ok(line)) => line,
+                _ => break,
+            };
+            let mut args = line.split_whitespace();
+            let command = match args.next() {
+                Some(command) => command,
+                None => continue,
+            };
+            let args = args.collect::<Vec<&str>>();
+            if let Err(msg) = self.execute(command, &args) {
+                if msg.is_empty() {
+                    break;
+                }
+                println!("{}", msg);
+            }
+        }
+    }
+
+    /// Executes a single command.
+    ///
+    /// Returns an empty error when the user wants to quit.
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<(), String> {
+        match command {
+            "step" | "s" => {
+                let count: usize = parse_arg(args.first().copied(), 1)?;
+                for _ in 0..count {
+                    if !self.step() {
+                        break;
+                    }
+                }
+                self.list();
+            }
+            "continue" | "c" => {
+                // always execute the current instruction, so that a breakpoint on it doesn't stop us
+                if self.step() {
+                    while !self.breakpoints.contains(&self.computer.pc()) && self.step() {}
+                }
+                if self.breakpoints.contains(&self.computer.pc()) {
+                    println!("Breakpoint at {}", self.computer.pc());
+                }
+                self.list();
+            }
+            "break" | "b" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                self.breakpoints.insert(address);
+            }
+            "delete" | "d" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                if !self.breakpoints.remove(&address) {
+                    return Err(format!("There is no breakpoint at {}", address));
+                }
+            }
+            "breakpoints" | "bl" => {
+                for address in &self.breakpoints {
+                    println!("{:04}", address);
+                }
+            }
+            "watch" | "w" | "rwatch" | "rw" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                let count: usize = parse_arg(args.get(1).copied(), 1)?;
+                let kind = match command {
+                    "watch" | "w" => WatchKind::Write,
+                    _ => WatchKind::Read,
+                };
+                self.computer.watch(locations(address, count)?, kind);
+            }
+            "vwatch" | "vw" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                let value: i64 = parse_arg(args.get(1).copied(), None)?;
+                self.computer
+                    .watch(locations(address, 1)?, WatchKind::WriteValue(value));
+            }
+            "unwatch" | "uw" => self.computer.clear_watchpoints(),
+            "registers" | "r" => println!(
+                "pc: {}, rb: {}",
+                self.computer.pc(),
+                self.computer.relative_base()
+            ),
+            "list" | "l" => self.list(),
+            "memory" | "x" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                let count: usize = parse_arg(args.get(1).copied(), 8)?;
+                if count > MEMORY_VIEW_LIMIT {
+                    return Err(format!(
+                        "Can't show more than {} locations at once",
+                        MEMORY_VIEW_LIMIT
+                    ));
+                }
+                let values = locations(address, count)?
+                    .map(|address| {
+                        self.computer
+                            .memory()
+                            .get(address)
+                            .map(|value| value.to_string())
+                    })
+                    .collect::<Result<Vec<String>, MemoryLimitExceeded>>()
+                    .map_err(|err| err.to_string())?;
+                println!("{:04}: {}", address, values.join(" "));
+            }
+            "poke" | "p" => {
+                let address: usize = parse_arg(args.first().copied(), None)?;
+                let value: i64 = parse_arg(args.get(1).copied(), None)?;
+                self.computer
+                    .memory_mut()
+                    .set(address, value)
+                    .map_err(|err| err.to_string())?;
+            }
+            "input" | "i" => {
+                if args.is_empty() {
+                    return Err(String::from("Expected at least one value"));
+                }
+                for arg in args {
+                    let value: i64 = parse_arg(Some(*arg), None)?;
+                    self.computer.push_input(value);
+                }
+            }
+            "output" | "o" => println!("{:?}", self.outputs),
+            "help" | "h" => println!("{}", HELP),
+            "quit" | "q" => return Err(String::new()),
+            _ => return Err(format!("Unknown command '{}' (try 'help')", command)),
+        }
+        Ok(())
+    }
+
+    /// Executes a single instruction, reporting any I/O and the end of the program.
+    ///
+    /// Returns whether the execution can continue.
+    fn step(&mut self) -> bool {
+        if self.state != State::Running {
+            println!("The program is no longer running");
+            return false;
+        }
+        let pc = self.computer.pc();
+        match self.computer.step() {
+            None => {}
+            Some(HaltReason::Output(value)) => {
+                println!("Output: {}", value);
+                self.outputs.push(value);
+            }
+            Some(HaltReason::Watchpoint(watchpoint)) => {
+                println!("{}", watchpoint);
+                // a watchpoint that was reported late (after an output) didn't execute an instruction
+                if watchpoint.pc == pc {
+                    self.push_history(pc);
+                }
+                return false;
+            }
+            Some(HaltReason::NeedsInput) => {
+                println!("The program is waiting for input (use 'input <value>')");
+                return false;
+            }
+            Some(HaltReason::Halted) => {
+                println!("The program terminated");
+                self.state = State::Halted;
+                return false;
+            }
+            Some(HaltReason::LimitExceeded(limit)) => {
+                println!("The program was stopped: {}", limit);
+                return false;
+            }
+            Some(HaltReason::Error(err)) => {
+                println!("The program failed: {}", err);
+                self.state = State::Faulted;
+                return false;
+            }
+        }
+        self.push_history(pc);
+        true
+    }
+
+    /// Records the location of an executed instruction.
+    fn push_history(&mut self, pc: usize) {
+        self.history.push_back(pc);
+        if self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+    }
+
+    /// Shows the last executed instructions, followed by the instructions starting at the program counter.
+    fn list(&self) {
+        for address in &self.history {
+            println!("   {}", self.decode(*address).0);
+        }
+        let mut address = self.computer.pc();
+        for idx in 0..LISTING_SIZE {
+            let (line, size) = self.decode(address);
+            let marker = if idx == 0 { "=>" } else { "  " };
+            println!("{} {}", marker, line);
+            address += size;
+        }
+    }
+
+    /// Decodes the instruction at the given address, returning its listing line and size.
+    fn decode(&self, address: usize) -> (String, usize) {
+        let words = (address..address + 4)
+            .map_while(|address| self.computer.memory().get(address).ok().cloned())
+            .collect::<Vec<i64>>();
+        let breakpoint = if self.breakpoints.contains(&address) {
+            '*'
+        } else {
+            ' '
+        };
+        match Instruction::decode(&words) {
+            Some(instruction) => {
+                let size = instruction.size();
+                let line = Line {
+                    address,
+                    words: words[..size.min(words.len())].to_vec(),
+                    content: LineContent::Code(instruction),
+                };
+                (format!("{}{}", breakpoint, line), size)
+            }
+            None => {
+                let line = Line {
+                    address,
+                    words: words.into_iter().take(1).collect(),
+                    content: LineContent::Data,
+                };
+                (format!("{}{}", breakpoint, line), 1)
+            }
+        }
+    }
+}
+
+/// Returns the range of `count` memory locations starting at the given address.
+fn locations(address: usize, count: usize) -> Result<Range<usize>, String> {
+    address
+        .checked_add(count)
+        .map(|end| address..end)
+        .ok_or_else(|| {
+            format!(
+                "The {} locations starting at {} can't be addressed",
+                count, address
+            )
+        })
+}
+
+/// Parses a command's argument, using the default value if it's missing.
+fn parse_arg<T: std::str::FromStr>(
+    arg: Option<&str>,
+    default: impl Into<Option<T>>,
+) -> Result<T, String> {
+    match (arg, default.into()) {
+        (Some(arg), _) => arg
+            .parse()
+            .map_err(|_| format!("Invalid argument '{}'", arg)),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(String::from("Missing argument (try 'help')")),
+    }
+}
@@ -0,0 +1,126 @@
+mod app;
+mod debugger;
+use app::*;
+use debugger::*;
+use intcode::*;
+use std::fs::read_to_string;
+
+fn load_program(contents: &str) -> Vec<i64> {
+    contents
+        .trim()
+        .split(',')
+        .map(|s| s.parse::<i64>().expect("Malformed input"))
+        .collect()
+}
+
+fn main() {
+    let opt: Opt = app().get_matches().into();
+    let contents = read_to_string(opt.path).expect("Failed to read the file");
+
+    match opt.command {
+        Command::Disassemble => print!("{}", disassemble(&load_program(&contents))),
+        Command::Assemble => match assemble::<i64>(&contents) {
+            Ok(memory) => println!(
+                "{}",
+                memory
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            Err(err) => eprintln!("Failed to assemble the program: {}", err),
+        },
+        Command::Analyze { dot } => {
+            let analysis = analyze(&load_program(&contents));
+            if dot {
+                print!("{}", analysis.cfg.to_dot());
+            } else {
+                print!("{}", analysis);
+            }
+        }
+        Command::Decompile { parameters } => {
+            print!("{}", decompile(&load_program(&contents), &parameters))
+        }
+        Command::Search {
+            variables,
+            target,
+            equals,
+            mode,
+            instructions,
+            threads,
+        } => {
+            let mut search = Search::new(load_program(&contents));
+            for (variable, values) in variables {
+                search.vary(variable, values);
+            }
+            search.set_mode(mode);
+            search.set_limits(Limits {
+                instructions: Some(instructions),
+                ..Limits::default()
+            });
+            if let Some(threads) = threads {
+                search.set_threads(threads);
+            }
+
+            let matches = match search.run(|candidate| {
+                let value = match target {
+                    Target::Memory(address) => candidate.memory.get(address).ok().copied(),
+                    Target::Output(Some(idx)) => candidate.outputs.get(idx).copied(),
+                    Target::Output(None) => candidate.outputs.last().copied(),
+                }?;
+                match equals {
+                    Some(equals) if value != equals => None,
+                    _ => Some(value),
+                }
+            }) {
+                Ok(matches) => matches,
+                Err(err) => return eprintln!("Failed to search: {}", err),
+            };
+            if matches.is_empty() {
+                println!("No matches");
+            }
+            for found in matches {
+                let values = search
+                    .variables()
+                    .zip(&found.values)
+                    .map(|(variable, value)| format!("{} = {}", variable, value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                println!("{}: {} = {}", values, target, found.key);
+            }
+        }
+        Command::Network { topology } => {
+            let topology: Topology = match read_to_string(topology)
+                .expect("Failed to read the topology file")
+                .parse()
+            {
+                Ok(topology) => topology,
+                Err(err) => return eprintln!("Malformed topology file: {}", err),
+            };
+            let names: Vec<&str> = topology.machines().collect();
+
+            let mut network = topology.network(&load_program(&contents));
+            match network.run() {
+                NetworkHalt::Halted => {}
+                NetworkHalt::Deadlock(waiting) => eprintln!(
+                    "The network deadlocked, with machines waiting for input: {}",
+                    waiting
+                        .iter()
+                        .map(|&machine| names[machine])
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ),
+                NetworkHalt::Error { machine, error } => {
+                    return eprintln!("Machine {} failed: {}", names[machine], error)
+                }
+            }
+
+            let result = topology.result();
+            match network.outputs(result).last() {
+                Some(value) => println!("Result: {}", value),
+                None => println!("Machine {} did not output anything", names[result]),
+            }
+        }
+        Command::Debug => Debugger::new(load_program(&contents)).run(),
+    }
+}
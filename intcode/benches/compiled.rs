@@ -0,0 +1,135 @@
+//! Measures the speed of the compiled backend against the interpreter:
+//! the day-2 noun and verb search, the day-5 diagnostic program and the day-7 amplifiers.
+//!
+//! Run with `cargo bench -p intcode --bench compiled`.
+
+use intcode::*;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+const DAY_2: &str = include_str!("../../day-2/src/input.txt");
+const DAY_5: &str = include_str!("../../day-5/src/input.txt");
+const DAY_7: &str = include_str!("../../day-7/src/input.txt");
+
+/// The number of times each workload is run.
+const ITERATIONS: u32 = 20;
+
+type Computer = IntcodeComputer<VecDeque<i64>, fn(i64)>;
+
+fn parse(program: &str) -> Vec<i64> {
+    program
+        .trim()
+        .split(',')
+        .map(|word| word.parse().unwrap())
+        .collect()
+}
+
+fn discard(_: i64) {}
+
+/// A single run of a program: the memory patches and the inputs.
+struct Run {
+    patch: Vec<(usize, i64)>,
+    inputs: Vec<i64>,
+}
+
+/// Every noun and verb of day 2.
+fn day_2() -> Vec<Run> {
+    (0..100)
+        .flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+        .map(|(noun, verb)| Run {
+            patch: vec![(1, noun), (2, verb)],
+            inputs: Vec::new(),
+        })
+        .collect()
+}
+
+/// The two system IDs of day 5.
+fn day_5() -> Vec<Run> {
+    [1, 5]
+        .iter()
+        .map(|id| Run {
+            patch: Vec::new(),
+            inputs: vec![*id],
+        })
+        .collect()
+}
+
+/// A single amplifier of day 7 for each phase setting and a few input signals.
+fn day_7() -> Vec<Run> {
+    (0..5)
+        .flat_map(|phase| (0..20).map(move |signal| vec![phase, signal * 1000]))
+        .map(|inputs| Run {
+            patch: Vec::new(),
+            inputs,
+        })
+        .collect()
+}
+
+/// Creates a computer for each run, with its memory patched and its inputs queued.
+fn setup(memory: &[i64], runs: &[Run]) -> Vec<Computer> {
+    runs.iter()
+        .map(|run| {
+            let mut computer: Computer =
+                IntcodeComputer::new(memory.to_vec(), VecDeque::new(), discard);
+            for (address, value) in &run.patch {
+                computer.poke(*address, *value).unwrap();
+            }
+            for input in &run.inputs {
+                computer.push_input(*input);
+            }
+            computer
+        })
+        .collect()
+}
+
+/// Returns the average time it takes to do all of the runs with the given backend,
+/// without the time it takes to create the computers.
+fn measure(memory: &[i64], runs: &[Run], compiled: bool) -> Duration {
+    let program = compile(memory);
+    let run_all = |computers: Vec<Computer>| {
+        for mut computer in computers {
+            // some of the day-2 nouns and verbs fail, with either backend
+            let _ = if compiled {
+                computer.run_compiled(&program)
+            } else {
+                computer.run()
+            };
+        }
+    };
+
+    // warm up
+    run_all(setup(memory, runs));
+    let mut total = Duration::default();
+    for _ in 0..ITERATIONS {
+        let computers = setup(memory, runs);
+        let start = Instant::now();
+        run_all(computers);
+        total += start.elapsed();
+    }
+    total / ITERATIONS
+}
+
+type Workload = fn() -> Vec<Run>;
+
+fn main() {
+    let workloads: [(&str, &str, Workload); 3] = [
+        ("day-2", DAY_2, day_2),
+        ("day-5", DAY_5, day_5),
+        ("day-7", DAY_7, day_7),
+    ];
+    for (name, program, runs) in workloads.iter() {
+        let memory = parse(program);
+        let runs = runs();
+        let interpreted = measure(&memory, &runs, false);
+        let compiled = measure(&memory, &runs, true);
+        println!(
+            "{}: {:?} interpreted, {:?} compiled ({:.2}x)",
+            name,
+            interpreted,
+            compiled,
+            interpreted.as_secs_f64() / compiled.as_secs_f64()
+        );
+    }
+}
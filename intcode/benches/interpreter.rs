@@ -0,0 +1,228 @@
+//! Measures the speed of the interpreter on the day-5 and day-7 programs,
+//! and the speed of decoding the instructions they execute with the original
+//! string-based decoder and the current arithmetic one.
+//!
+//! Run with `cargo bench -p intcode --bench interpreter`.
+
+use intcode::*;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const DAY_5: &str = include_str!("../../day-5/src/input.txt");
+const DAY_7: &str = include_str!("../../day-7/src/input.txt");
+
+/// The number of times each program is run.
+const ITERATIONS: u32 = 500;
+
+/// An input stream for programs whose input is queued in advance.
+struct NoInput;
+
+impl Input<i64> for NoInput {
+    fn get(&mut self) -> Result<i64, StreamError> {
+        panic!("The program needed more input than was queued")
+    }
+}
+
+/// An output stream that discards the values.
+struct Discard;
+
+impl Output<i64> for Discard {
+    fn push(&mut self, _value: i64) {}
+}
+
+fn parse(program: &str) -> Vec<i64> {
+    program
+        .trim()
+        .split(',')
+        .map(|word| word.parse().unwrap())
+        .collect()
+}
+
+fn computer(memory: &[i64]) -> IntcodeComputer<NoInput, Discard> {
+    IntcodeComputer::new(memory.to_vec(), NoInput, Discard)
+}
+
+/// Creates the day-5 diagnostic program, running the thermal radiator system (5).
+fn day_5(memory: &[i64]) -> Vec<Vec<IntcodeComputer<NoInput, Discard>>> {
+    let mut computer = computer(memory);
+    computer.push_input(5);
+    vec![vec![computer]]
+}
+
+/// Creates the day-7 amplifiers for each permutation of the feedback loop phase settings.
+fn day_7(memory: &[i64]) -> Vec<Vec<IntcodeComputer<NoInput, Discard>>> {
+    permutations(vec![5, 6, 7, 8, 9])
+        .into_iter()
+        .map(|phases| {
+            phases
+                .into_iter()
+                .map(|phase| {
+                    let mut amplifier = computer(memory);
+                    amplifier.push_input(phase);
+                    amplifier
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Runs the amplifiers of each group in a feedback loop (a single amplifier simply runs its program).
+///
+/// Returns the amplifiers once they terminated.
+fn run<B: Observer<i64>>(
+    groups: Vec<Vec<IntcodeComputer<NoInput, Discard, i64, B>>>,
+) -> Vec<Vec<IntcodeComputer<NoInput, Discard, i64, B>>> {
+    let mut finished = Vec::new();
+    for mut amplifiers in groups {
+        let mut signal = 0;
+        'feedback: loop {
+            for amplifier in &mut amplifiers {
+                amplifier.push_input(signal);
+                match amplifier.run_until_io() {
+                    HaltReason::Output(value) => signal = value,
+                    HaltReason::Halted => break 'feedback,
+                    reason => panic!("The program failed: {:?}", reason),
+                }
+            }
+        }
+        finished.push(amplifiers);
+    }
+    finished
+}
+
+fn permutations(values: Vec<i64>) -> Vec<Vec<i64>> {
+    if values.len() <= 1 {
+        return vec![values];
+    }
+    let mut result = Vec::new();
+    for idx in 0..values.len() {
+        let mut rest = values.clone();
+        let first = rest.remove(idx);
+        for mut permutation in permutations(rest) {
+            permutation.insert(0, first);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+/// Returns the average time it takes to run the computers created by `setup`,
+/// without the time it takes to create them.
+fn measure(setup: impl Fn() -> Vec<Vec<IntcodeComputer<NoInput, Discard>>>) -> Duration {
+    // warm up
+    run(setup());
+    let mut total = Duration::default();
+    for _ in 0..ITERATIONS {
+        let groups = setup();
+        let start = Instant::now();
+        run(groups);
+        total += start.elapsed();
+    }
+    total / ITERATIONS
+}
+
+/// An observer that records the instructions as they are fetched.
+#[derive(Default)]
+struct Trace(Vec<(usize, i64)>);
+
+impl Observer<i64> for Trace {
+    fn on_fetch(&mut self, pc: usize, instruction: &i64) {
+        self.0.push((pc, *instruction));
+    }
+}
+
+/// Returns the instructions that each of the computers created by `setup` fetches, with their locations.
+fn trace(
+    setup: impl Fn() -> Vec<Vec<IntcodeComputer<NoInput, Discard>>>,
+) -> Vec<Vec<(usize, i64)>> {
+    let groups = setup()
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|computer| computer.with_observer(Trace::default()))
+                .collect()
+        })
+        .collect();
+    run(groups)
+        .into_iter()
+        .flatten()
+        .map(|computer| computer.into_observer().0)
+        .collect()
+}
+
+/// The opcode and the parameter modes of an instruction.
+type Decoded = (i64, [i64; 3]);
+
+/// The original decoder, which formats the parameter modes as a string and parses each digit.
+fn decode_string(instruction: i64) -> Option<Decoded> {
+    if instruction < 0 {
+        return None;
+    }
+    let modes = (instruction / 100)
+        .to_string()
+        .chars()
+        .rev()
+        .map(|c| i64::from(c.to_digit(10).unwrap()))
+        .collect::<Vec<i64>>();
+    let mode = |idx: usize| *modes.get(idx).unwrap_or(&0);
+    Some((instruction % 100, [mode(0), mode(1), mode(2)]))
+}
+
+/// The current decoder, which takes the parameter modes digit by digit arithmetically.
+fn decode_digits(instruction: i64) -> Option<Decoded> {
+    if instruction < 0 {
+        return None;
+    }
+    let mut modes = [0; 3];
+    let mut digits = instruction / 100;
+    let mut idx = 0;
+    while digits > 0 {
+        if idx < modes.len() {
+            modes[idx] = digits % 10;
+        }
+        digits /= 10;
+        idx += 1;
+    }
+    Some((instruction % 100, modes))
+}
+
+/// Returns the average time it takes to decode every instruction of the traces.
+fn measure_decoder(traces: &[Vec<(usize, i64)>], decode: fn(i64) -> Option<Decoded>) -> Duration {
+    let mut total = Duration::default();
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        for trace in traces {
+            for (_, instruction) in trace {
+                black_box(decode(black_box(*instruction)).unwrap());
+            }
+        }
+        total += start.elapsed();
+    }
+    total / ITERATIONS
+}
+
+type Setup = fn(&[i64]) -> Vec<Vec<IntcodeComputer<NoInput, Discard>>>;
+
+fn main() {
+    let programs: [(&str, Vec<i64>, Setup); 2] = [
+        ("day-5", parse(DAY_5), day_5),
+        ("day-7", parse(DAY_7), day_7),
+    ];
+    for (name, memory, setup) in programs.iter() {
+        let traces = trace(|| setup(memory));
+        let string = measure_decoder(&traces, decode_string);
+        let digits = measure_decoder(&traces, decode_digits);
+        println!(
+            "{}: decoding {} instructions takes {:?} with strings, {:?} with digits ({:.2}x)",
+            name,
+            traces.iter().map(Vec::len).sum::<usize>(),
+            string,
+            digits,
+            string.as_secs_f64() / digits.as_secs_f64()
+        );
+        println!("{}: running takes {:?}", name, measure(|| setup(memory)));
+    }
+}
@@ -0,0 +1,515 @@
+use crate::{Instruction, Opcode, Operand, Word};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter, Write},
+    ops::Range,
+};
+
+/// How the execution continues after the last instruction of a [`BasicBlock`].
+///
+/// [`BasicBlock`]: struct.BasicBlock.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockExit {
+    /// The execution falls through to the block that starts at the given location.
+    FallThrough(usize),
+    /// A `JumpIfTrue` or `JumpIfFalse` instruction.
+    ///
+    /// `target` is `None` if the jump's location isn't known statically (a *dynamic* jump),
+    /// and `fall_through` is `None` if the condition is an *immediate* parameter that always jumps.
+    Branch {
+        target: Option<usize>,
+        fall_through: Option<usize>,
+    },
+    /// A `Terminate` instruction.
+    Halt,
+    /// The instruction at the given location always fails: its opcode or one of its
+    /// parameter modes is invalid, or it writes to an *immediate* parameter.
+    Fault(usize),
+}
+
+/// A basic block: a sequence of instructions that is only entered at its start,
+/// and only left after its last instruction.
+#[derive(Clone, Debug)]
+pub struct BasicBlock<W: Word> {
+    /// The memory location of the first instruction of the block.
+    pub start: usize,
+    /// The instructions of the block, including the jump or terminate instruction that ends it.
+    pub instructions: Vec<Instruction<W>>,
+    /// How the execution continues after the block.
+    pub exit: BlockExit,
+}
+
+impl<W: Word> BasicBlock<W> {
+    /// Returns the location that follows the last instruction of the block.
+    pub fn end(&self) -> usize {
+        self.start
+            + self
+                .instructions
+                .iter()
+                .map(|instruction| instruction.size())
+                .sum::<usize>()
+    }
+
+    /// Returns the starting locations of the blocks that may follow this block.
+    ///
+    /// The targets of dynamic jumps are unknown, and so aren't included.
+    pub fn successors(&self) -> Vec<usize> {
+        match &self.exit {
+            BlockExit::FallThrough(next) => vec![*next],
+            BlockExit::Branch {
+                target,
+                fall_through,
+            } => target.iter().chain(fall_through).cloned().collect(),
+            BlockExit::Halt | BlockExit::Fault(_) => vec![],
+        }
+    }
+
+    // helper function to iterate over the instructions along with their locations
+    pub(crate) fn located(&self) -> impl Iterator<Item = (usize, &Instruction<W>)> {
+        self.instructions
+            .iter()
+            .scan(self.start, |address, instruction| {
+                let located = (*address, instruction);
+                *address += instruction.size();
+                Some(located)
+            })
+    }
+}
+
+/// The control-flow graph of an Intcode program.
+///
+/// Created by [`analyze`].
+///
+/// [`analyze`]: fn.analyze.html
+#[derive(Clone, Debug)]
+pub struct ControlFlowGraph<W: Word> {
+    /// The reachable basic blocks, ordered by their starting location.
+    pub blocks: Vec<BasicBlock<W>>,
+}
+
+impl<W: Word> ControlFlowGraph<W> {
+    /// Returns the block that starts at the given location.
+    pub fn block_at(&self, start: usize) -> Option<&BasicBlock<W>> {
+        self.blocks
+            .binary_search_by_key(&start, |block| block.start)
+            .ok()
+            .map(|idx| &self.blocks[idx])
+    }
+
+    /// Exports the graph in the Graphviz DOT format.
+    ///
+    /// Each block is a node listing its instructions, and the edges of branches
+    /// are labeled with the condition that takes them.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::analyze;
+    ///
+    /// let dot = analyze(&[1105, 1, 4, 42, 104, 3, 99]).cfg.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph intcode {"));
+    /// assert!(dot.contains("b0 -> b4 [label=\"always\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        // writing to a string can't fail
+        self.write_dot(&mut dot).unwrap();
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String) -> fmt::Result {
+        writeln!(dot, "digraph intcode {{")?;
+        writeln!(dot, "    node [shape=box, fontname=\"monospace\"];")?;
+        let mut has_dynamic = false;
+        for block in &self.blocks {
+            let mut label = String::new();
+            for (address, instruction) in block.located() {
+                write!(label, "{:04}  {}\\l", address, instruction)?;
+            }
+            if let BlockExit::Fault(address) = block.exit {
+                write!(label, "{:04}  fault\\l", address)?;
+            }
+            writeln!(dot, "    b{} [label=\"{}\"];", block.start, label)?;
+
+            match &block.exit {
+                BlockExit::FallThrough(next) => {
+                    writeln!(dot, "    b{} -> b{};", block.start, next)?
+                }
+                BlockExit::Branch {
+                    target,
+                    fall_through,
+                } => {
+                    let (jump, fall) = match fall_through {
+                        Some(_) => ("jump", "no jump"),
+                        None => ("always", ""),
+                    };
+                    match target {
+                        Some(target) => writeln!(
+                            dot,
+                            "    b{} -> b{} [label=\"{}\"];",
+                            block.start, target, jump
+                        )?,
+                        None => {
+                            has_dynamic = true;
+                            writeln!(dot, "    b{} -> dynamic [style=dashed];", block.start)?
+                        }
+                    }
+                    if let Some(next) = fall_through {
+                        writeln!(
+                            dot,
+                            "    b{} -> b{} [label=\"{}\"];",
+                            block.start, next, fall
+                        )?;
+                    }
+                }
+                BlockExit::Halt | BlockExit::Fault(_) => {}
+            }
+        }
+        if has_dynamic {
+            writeln!(dot, "    dynamic [label=\"dynamic jump\", shape=ellipse];")?;
+        }
+        writeln!(dot, "}}")
+    }
+}
+
+/// An instruction that writes to the code of the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfModification {
+    /// The location of the writing instruction.
+    pub pc: usize,
+    /// The location of the code that is overwritten.
+    pub address: usize,
+}
+
+/// The results of a static analysis of an Intcode program.
+///
+/// Created by [`analyze`].
+///
+/// The analysis only knows the locations of *position* parameters:
+/// the locations of *relative* parameters depend on the relative base at run time,
+/// and the targets of dynamic jumps are never followed. As such, the code that is
+/// reported as unreachable may still be reached through a dynamic jump, and the
+/// reported self-modifications and input destinations may be incomplete.
+///
+/// When the program overwrites a word that can't be decoded before reaching it,
+/// the code that follows is only known at run time: it is reported as unknown
+/// rather than unreachable.
+///
+/// [`analyze`]: fn.analyze.html
+#[derive(Clone, Debug)]
+pub struct Analysis<W: Word> {
+    /// The control-flow graph of the reachable code.
+    pub cfg: ControlFlowGraph<W>,
+    /// The ranges of locations that aren't part of any reachable instruction (data, or dead code).
+    pub unreachable: Vec<Range<usize>>,
+    /// The ranges of locations whose code is unknown, since they start with a reachable word
+    /// that can't be decoded yet is overwritten by the program (self-modified).
+    pub unknown: Vec<Range<usize>>,
+    /// The instructions that write to the reachable code.
+    pub self_modifications: Vec<SelfModification>,
+    /// The locations that `Input` instructions write to.
+    pub input_destinations: BTreeSet<usize>,
+    /// The locations of the jumps whose target isn't known statically.
+    pub dynamic_jumps: Vec<usize>,
+}
+
+impl<W: Word> Display for Analysis<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // helper function to join a list of items
+        fn join<T: Display>(items: impl Iterator<Item = T>) -> String {
+            let items = items.map(|item| item.to_string()).collect::<Vec<String>>();
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items.join(", ")
+            }
+        }
+
+        writeln!(f, "Basic blocks: {}", self.cfg.blocks.len())?;
+        writeln!(
+            f,
+            "Unreachable locations: {}",
+            join(
+                self.unreachable
+                    .iter()
+                    .map(|range| format!("{}..{}", range.start, range.end))
+            )
+        )?;
+        writeln!(
+            f,
+            "Unknown locations (self-modified): {}",
+            join(
+                self.unknown
+                    .iter()
+                    .map(|range| format!("{}..{}", range.start, range.end))
+            )
+        )?;
+        writeln!(
+            f,
+            "Self-modification sites: {}",
+            join(
+                self.self_modifications
+                    .iter()
+                    .map(|site| format!("{} -> {}", site.pc, site.address))
+            )
+        )?;
+        writeln!(
+            f,
+            "Input destinations: {}",
+            join(self.input_destinations.iter())
+        )?;
+        writeln!(f, "Dynamic jumps: {}", join(self.dynamic_jumps.iter()))
+    }
+}
+
+/// Analyzes the given memory image, without running it.
+///
+/// The control-flow graph is built by following the program from location `0`,
+/// including the targets of jumps whose location is an *immediate* parameter.
+/// See [`Analysis`] for the limits of the analysis.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let analysis = analyze(&[3, 12, 1006, 12, 11, 1101, 0, 99, 11, 4, 12, 99, 0]);
+///
+/// assert_eq!(analysis.cfg.blocks.len(), 3);
+/// assert_eq!(analysis.unreachable, vec![12..13]);
+/// assert_eq!(analysis.self_modifications, vec![SelfModification { pc: 5, address: 11 }]);
+/// assert!(analysis.input_destinations.contains(&12));
+///
+/// // the word at 4 is only decodable once the first instruction overwrote it
+/// let analysis = analyze(&[1101, 100, -1, 4, 0, 0, 99]);
+///
+/// assert!(analysis.unreachable.is_empty());
+/// assert_eq!(analysis.unknown, vec![4..7]);
+/// ```
+///
+/// [`Analysis`]: struct.Analysis.html
+pub fn analyze<W: Word>(memory: &[W]) -> Analysis<W> {
+    // find the reachable instructions, and the locations where blocks start
+    let mut decoded = BTreeMap::new();
+    let mut leaders = BTreeSet::new();
+    leaders.insert(0);
+    let mut pending = vec![0];
+    while let Some(address) = pending.pop() {
+        if decoded.contains_key(&address) {
+            continue;
+        }
+        let step = decode(memory, address);
+        match &step {
+            Step::Continue(_) => pending.push(address + step.size()),
+            Step::Branch(_, target, fall_through) => {
+                for next in target.iter().chain(fall_through) {
+                    leaders.insert(*next);
+                    pending.push(*next);
+                }
+            }
+            Step::Halt(_) | Step::Fault => {}
+        }
+        decoded.insert(address, step);
+    }
+
+    // split the instructions into blocks
+    let mut blocks = Vec::new();
+    for start in &leaders {
+        let mut block = BasicBlock {
+            start: *start,
+            instructions: Vec::new(),
+            exit: BlockExit::Halt,
+        };
+        let mut address = *start;
+        block.exit = loop {
+            if address != *start && leaders.contains(&address) {
+                break BlockExit::FallThrough(address);
+            }
+            match &decoded[&address] {
+                Step::Continue(instruction) => {
+                    block.instructions.push(instruction.clone());
+                    address += instruction.size();
+                }
+                Step::Branch(instruction, target, fall_through) => {
+                    block.instructions.push(instruction.clone());
+                    break BlockExit::Branch {
+                        target: *target,
+                        fall_through: *fall_through,
+                    };
+                }
+                Step::Halt(instruction) => {
+                    block.instructions.push(instruction.clone());
+                    break BlockExit::Halt;
+                }
+                Step::Fault => break BlockExit::Fault(address),
+            }
+        };
+        blocks.push(block);
+    }
+    let cfg = ControlFlowGraph { blocks };
+
+    // mark the words of the reachable instructions as code, including the faulting words:
+    // a program may write a valid instruction over them before they are reached
+    let mut code = vec![false; memory.len()];
+    for block in &cfg.blocks {
+        if let BlockExit::Fault(address) = block.exit {
+            if let Some(is_code) = code.get_mut(address) {
+                *is_code = true;
+            }
+        }
+        for (address, instruction) in block.located() {
+            let end = (address + instruction.size()).min(code.len());
+            if address < end {
+                code[address..end]
+                    .iter_mut()
+                    .for_each(|is_code| *is_code = true);
+            }
+        }
+    }
+    let mut unreachable: Vec<Range<usize>> = Vec::new();
+    for address in (0..code.len()).filter(|address| !code[*address]) {
+        match unreachable.last_mut() {
+            Some(range) if range.end == address => range.end += 1,
+            _ => unreachable.push(address..address + 1),
+        }
+    }
+
+    // find the writes to the code and the input destinations
+    let mut self_modifications = Vec::new();
+    let mut input_destinations = BTreeSet::new();
+    let mut dynamic_jumps = Vec::new();
+    for block in &cfg.blocks {
+        for (pc, instruction) in block.located() {
+            // every reachable instruction has a valid opcode
+            let opcode = opcode_at(memory, pc).unwrap();
+            let destination = match instruction.operands.last() {
+                Some(Operand::Position(dst)) if writes(opcode) => dst.to_address(),
+                _ => None,
+            };
+            if let Some(address) = destination {
+                if code.get(address).copied().unwrap_or(false) {
+                    self_modifications.push(SelfModification { pc, address });
+                }
+                if let Opcode::Input(_) = opcode {
+                    input_destinations.insert(address);
+                }
+            }
+        }
+        if let BlockExit::Branch { target: None, .. } = block.exit {
+            // the jump is the last instruction of the block
+            let pc = block.end() - block.instructions.last().map_or(0, |jump| jump.size());
+            dynamic_jumps.push(pc);
+        }
+    }
+
+    // the code from an overwritten fault up to the next known code is only known at run time
+    let mut unknown = Vec::new();
+    for block in &cfg.blocks {
+        let address = match block.exit {
+            BlockExit::Fault(address) => address,
+            _ => continue,
+        };
+        if !self_modifications
+            .iter()
+            .any(|site| site.address == address)
+        {
+            continue;
+        }
+        let end = match unreachable
+            .iter()
+            .position(|range| range.start == address + 1)
+        {
+            Some(idx) => unreachable.remove(idx).end,
+            None => address + 1,
+        };
+        unknown.push(address..end);
+    }
+    unknown.sort_by_key(|range| range.start);
+
+    Analysis {
+        cfg,
+        unreachable,
+        unknown,
+        self_modifications,
+        input_destinations,
+        dynamic_jumps,
+    }
+}
+
+/// A reachable instruction, and where the execution continues after it.
+#[derive(Clone, Debug)]
+enum Step<W: Word> {
+    Continue(Instruction<W>),
+    Branch(Instruction<W>, Option<usize>, Option<usize>),
+    Halt(Instruction<W>),
+    Fault,
+}
+
+impl<W: Word> Step<W> {
+    fn size(&self) -> usize {
+        match self {
+            Step::Continue(instruction)
+            | Step::Branch(instruction, _, _)
+            | Step::Halt(instruction) => instruction.size(),
+            Step::Fault => 1,
+        }
+    }
+}
+
+/// Decodes the instruction at the given location.
+///
+/// Parameters past the end of the memory are decoded as zero, as they are at run time.
+fn decode<W: Word>(memory: &[W], address: usize) -> Step<W> {
+    let opcode = match opcode_at(memory, address) {
+        Some(opcode) => opcode,
+        None => return Step::Fault,
+    };
+    let instruction = Instruction::decode(&memory[address..]).unwrap();
+    let end = address + instruction.size();
+
+    // instructions never write to an immediate parameter
+    let immediate_write =
+        writes(opcode) && matches!(instruction.operands.last(), Some(Operand::Immediate(_)));
+    if immediate_write {
+        return Step::Fault;
+    }
+
+    match opcode {
+        Opcode::Terminate => Step::Halt(instruction),
+        Opcode::JumpIfTrue(_, _) | Opcode::JumpIfFalse(_, _) => {
+            let target = match &instruction.operands[1] {
+                Operand::Immediate(target) => target.to_address(),
+                _ => None,
+            };
+            // an immediate condition that always jumps has no fall through
+            let always_jumps = match &instruction.operands[0] {
+                Operand::Immediate(cond) => match opcode {
+                    Opcode::JumpIfTrue(_, _) => !cond.is_zero(),
+                    _ => cond.is_zero(),
+                },
+                _ => false,
+            };
+            let fall_through = if always_jumps { None } else { Some(end) };
+            Step::Branch(instruction, target, fall_through)
+        }
+        _ => Step::Continue(instruction),
+    }
+}
+
+/// Decodes the opcode at the given location.
+pub(crate) fn opcode_at<W: Word>(memory: &[W], address: usize) -> Option<Opcode> {
+    memory
+        .get(address)
+        .and_then(|word| word.to_i64())
+        .and_then(|i| Opcode::from_i64(i).ok())
+}
+
+/// Returns `true` if the last parameter of the opcode is the location it writes to.
+pub(crate) fn writes(opcode: Opcode) -> bool {
+    use Opcode::*;
+    match opcode {
+        Add(_, _, _) | Multiply(_, _, _) | Input(_) | LessThan(_, _, _) | Equals(_, _, _) => true,
+        Output(_) | JumpIfTrue(_, _) | JumpIfFalse(_, _) | AdjustRelativeBase(_) | Terminate => {
+            false
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Input, Output, StreamError, Word};
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, StdinLock, StdoutLock, Write},
+};
+
+/// An [`Input`] that feeds a text stream to the program as character codes.
+///
+/// The stream is read a line at a time, and each line is followed by a newline (`10`),
+/// even if it was the last one and had no line terminator.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let mut input = AsciiInput::new("hi\nno".as_bytes());
+/// let codes: Vec<i64> = (0..6).map(|_| input.get().unwrap()).collect();
+///
+/// assert_eq!(codes, vec![104, 105, 10, 110, 111, 10]);
+/// assert!(Input::<i64>::get(&mut input).is_err());
+/// ```
+///
+/// [`Input`]: trait.Input.html
+#[derive(Debug)]
+pub struct AsciiInput<R: BufRead> {
+    reader: R,
+    pending: VecDeque<i64>,
+}
+
+impl<R: BufRead> AsciiInput<R> {
+    /// Creates an input that reads the text from the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Consumes the input, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl AsciiInput<StdinLock<'static>> {
+    /// Creates an input that reads the text from `Stdin`.
+    pub fn stdin() -> Self {
+        Self::new(io::stdin().lock())
+    }
+}
+
+impl<W: Word, R: BufRead> Input<W> for AsciiInput<R> {
+    fn get(&mut self) -> Result<W, StreamError> {
+        if self.pending.is_empty() {
+            let mut buffer = String::new();
+            if self.reader.read_line(&mut buffer)? == 0 {
+                return Err(StreamError {
+                    msg: String::from("The input stream was closed"),
+                });
+            }
+            let line = buffer.trim_end_matches(&['\r', '\n'][..]);
+            self.pending
+                .extend(line.chars().map(|c| i64::from(u32::from(c))));
+            self.pending.push_back(10);
+        }
+        Ok(W::from_i64(self.pending.pop_front().unwrap()))
+    }
+}
+
+/// An [`Output`] that renders the values of the program as text.
+///
+/// Values in the ASCII range (`0` to `127`) are written as characters,
+/// and any other value is written as a number on its own line.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let mut output = AsciiOutput::new(Vec::new());
+/// for value in &[104, 105, 10, 1_000_000] {
+///     output.push(*value);
+/// }
+///
+/// assert_eq!(output.into_inner(), b"hi\n1000000\n");
+/// ```
+///
+/// [`Output`]: trait.Output.html
+#[derive(Debug)]
+pub struct AsciiOutput<Wr: Write> {
+    writer: Wr,
+}
+
+impl<Wr: Write> AsciiOutput<Wr> {
+    /// Creates an output that writes the text to the given writer.
+    pub fn new(writer: Wr) -> Self {
+        Self { writer }
+    }
+
+    /// Consumes the output, returning the underlying writer.
+    pub fn into_inner(self) -> Wr {
+        self.writer
+    }
+}
+
+impl AsciiOutput<StdoutLock<'static>> {
+    /// Creates an output that writes the text to `Stdout`.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+}
+
+impl<W: Word, Wr: Write> Output<W> for AsciiOutput<Wr> {
+    fn push(&mut self, value: W) {
+        match value.to_i64() {
+            Some(code @ 0..=127) => self.writer.write_all(&[code as u8]).unwrap(),
+            _ => self
+                .writer
+                .write_all(format!("{}\n", value).as_bytes())
+                .unwrap(),
+        }
+        // flush so that prompts show up before the program waits for input
+        self.writer.flush().unwrap();
+    }
+}
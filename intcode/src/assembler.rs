@@ -0,0 +1,574 @@
+use crate::Word;
+use std::{
+    collections::{HashMap, HashSet},
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error which can occour while assembling a program.
+///
+/// The error points to the line and column (both starting at 1) that caused it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssemblyError {
+    /// The line at which the error occurred.
+    pub line: usize,
+    /// The column at which the error occurred.
+    pub column: usize,
+    msg: String,
+}
+
+impl AssemblyError {
+    fn new(line: usize, column: usize, msg: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            msg: msg.into(),
+        }
+    }
+}
+
+impl Display for AssemblyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.msg)
+    }
+}
+
+impl error::Error for AssemblyError {}
+
+/// Assembles the given source into a memory image for the [`IntcodeComputer`].
+///
+/// # Syntax
+/// Each line holds an optional label (`name:`), followed by an optional statement.
+/// Everything after a `;` is a comment.
+///
+/// The statements are:
+/// - an instruction: a mnemonic (`add`, `mul`, `in`, `out`, `jt`, `jf`, `lt`, `eq`, `arb`, `hlt`)
+///   followed by comma-separated operands.
+/// - `data v1, v2, ...` - places the given values in memory.
+/// - `const NAME = value` - defines a constant.
+///
+/// The operands use the same syntax as the disassembler:
+/// - `[x]` - *position* mode.
+/// - `#x` - *immediate* mode.
+/// - `rb+x` (or `rb-x`) - *relative* mode.
+///
+/// Values are numbers, labels and constants, optionally added or subtracted (e.g. `buffer+2`).
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let source = "
+///     const STEP = 10
+///         in [x]
+///         add [x], #STEP, [x]     ; increment the input
+///         out [x]
+///         hlt
+///     x:  data 0
+/// ";
+/// let memory: Vec<i64> = assemble(source).unwrap();
+///
+/// assert_eq!(memory, vec![3, 9, 1001, 9, 10, 9, 4, 9, 99, 0]);
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+pub fn assemble<W: Word>(source: &str) -> Result<Vec<W>, AssemblyError> {
+    // parse the source
+    let mut statements = Vec::new();
+    let mut symbols = Symbols::default();
+    let mut address = 0i64;
+    for (idx, text) in source.lines().enumerate() {
+        let line = idx + 1;
+        let mut parser = Parser::new(line, text)?;
+
+        // define the labels
+        while let Some((name, column)) = parser.label() {
+            symbols.define(&name, line, column, Symbol::Label(address))?;
+        }
+
+        match parser.statement()? {
+            Some(Statement::Constant {
+                name,
+                column,
+                value,
+            }) => symbols.define(&name, line, column, Symbol::Constant(value))?,
+            Some(statement) => {
+                address += statement.size() as i64;
+                statements.push(statement);
+            }
+            None => {}
+        }
+    }
+
+    // encode the statements now that all the symbols are known
+    let mut memory = Vec::new();
+    for statement in statements {
+        match statement {
+            Statement::Instruction { opcode, operands } => {
+                let mut instruction = opcode.code;
+                let mut params = Vec::new();
+                for (idx, operand) in operands.iter().enumerate() {
+                    instruction += operand.mode * 10i64.pow(idx as u32 + 2);
+                    params.push(symbols.evaluate_word(&operand.value)?);
+                }
+                // the opcode and the modes are at most 5 digits, which fit in any word
+                memory.push(W::from_i64(instruction));
+                memory.extend(params);
+            }
+            Statement::Data(values) => {
+                for value in values {
+                    memory.push(symbols.evaluate_word(&value)?);
+                }
+            }
+            Statement::Constant { .. } => unreachable!(),
+        }
+    }
+    Ok(memory)
+}
+
+/// The description of an instruction's mnemonic.
+#[derive(Debug)]
+struct Mnemonic {
+    name: &'static str,
+    code: i64,
+    /// The number of parameters of the instruction.
+    params: usize,
+    /// The index of the parameter the instruction writes to.
+    dst: Option<usize>,
+}
+
+impl Mnemonic {
+    const fn new(name: &'static str, code: i64, params: usize, dst: Option<usize>) -> Self {
+        Self {
+            name,
+            code,
+            params,
+            dst,
+        }
+    }
+}
+
+const MNEMONICS: [Mnemonic; 10] = [
+    Mnemonic::new("add", 1, 3, Some(2)),
+    Mnemonic::new("mul", 2, 3, Some(2)),
+    Mnemonic::new("in", 3, 1, Some(0)),
+    Mnemonic::new("out", 4, 1, None),
+    Mnemonic::new("jt", 5, 2, None),
+    Mnemonic::new("jf", 6, 2, None),
+    Mnemonic::new("lt", 7, 3, Some(2)),
+    Mnemonic::new("eq", 8, 3, Some(2)),
+    Mnemonic::new("arb", 9, 1, None),
+    Mnemonic::new("hlt", 99, 0, None),
+];
+
+/// A value made of numbers and symbols, e.g. `buffer+2`.
+#[derive(Debug)]
+struct Expr {
+    line: usize,
+    column: usize,
+    terms: Vec<(i64, Term)>,
+}
+
+#[derive(Debug)]
+enum Term {
+    Number(i64),
+    Symbol { name: String, column: usize },
+}
+
+#[derive(Debug)]
+struct Operand {
+    mode: i64,
+    value: Expr,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instruction {
+        opcode: &'static Mnemonic,
+        operands: Vec<Operand>,
+    },
+    Data(Vec<Expr>),
+    Constant {
+        name: String,
+        column: usize,
+        value: Expr,
+    },
+}
+
+impl Statement {
+    fn size(&self) -> usize {
+        match self {
+            Statement::Instruction { operands, .. } => operands.len() + 1,
+            Statement::Data(values) => values.len(),
+            Statement::Constant { .. } => 0,
+        }
+    }
+}
+
+enum Symbol {
+    Label(i64),
+    Constant(Expr),
+}
+
+/// The labels and constants of a program.
+#[derive(Default)]
+struct Symbols {
+    symbols: HashMap<String, Symbol>,
+}
+
+impl Symbols {
+    fn define(
+        &mut self,
+        name: &str,
+        line: usize,
+        column: usize,
+        symbol: Symbol,
+    ) -> Result<(), AssemblyError> {
+        let reserved = ["rb", "data", "const"];
+        if reserved.contains(&name) || MNEMONICS.iter().any(|mnemonic| mnemonic.name == name) {
+            return Err(AssemblyError::new(
+                line,
+                column,
+                format!("'{}' is a reserved word", name),
+            ));
+        }
+        if self.symbols.insert(name.to_owned(), symbol).is_some() {
+            return Err(AssemblyError::new(
+                line,
+                column,
+                format!("'{}' is already defined", name),
+            ));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, expr: &Expr) -> Result<i64, AssemblyError> {
+        self.evaluate_impl(expr, &mut HashSet::new())
+    }
+
+    fn evaluate_word<W: Word>(&self, expr: &Expr) -> Result<W, AssemblyError> {
+        W::checked_from_i64(self.evaluate(expr)?).ok_or_else(|| {
+            AssemblyError::new(expr.line, expr.column, "The value does not fit in a word")
+        })
+    }
+
+    fn evaluate_impl<'a>(
+        &'a self,
+        expr: &'a Expr,
+        visiting: &mut HashSet<&'a str>,
+    ) -> Result<i64, AssemblyError> {
+        let mut result = 0i64;
+        for (sign, term) in &expr.terms {
+            let value = match term {
+                Term::Number(value) => *value,
+                Term::Symbol { name, column } => {
+                    let error = |msg: String| AssemblyError::new(expr.line, *column, msg);
+                    match self.symbols.get(name) {
+                        Some(Symbol::Label(address)) => *address,
+                        Some(Symbol::Constant(value)) => {
+                            if !visiting.insert(name) {
+                                return Err(error(format!(
+                                    "'{}' is defined in terms of itself",
+                                    name
+                                )));
+                            }
+                            let value = self.evaluate_impl(value, visiting)?;
+                            visiting.remove(name.as_str());
+                            value
+                        }
+                        None => return Err(error(format!("'{}' is not defined", name))),
+                    }
+                }
+            };
+            result = value
+                .checked_mul(*sign)
+                .and_then(|value| result.checked_add(value))
+                .ok_or_else(|| {
+                    AssemblyError::new(expr.line, expr.column, "The value is too large")
+                })?;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(i64),
+    Punct(char),
+}
+
+/// Parses a single line of the source.
+struct Parser {
+    line: usize,
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    /// The column just past the end of the line.
+    end: usize,
+}
+
+impl Parser {
+    fn new(line: usize, text: &str) -> Result<Self, AssemblyError> {
+        // remove the comment
+        let text = match text.find(';') {
+            Some(idx) => &text[..idx],
+            None => text,
+        };
+
+        // split the line into tokens
+        let chars = text.chars().collect::<Vec<char>>();
+        let mut tokens = Vec::new();
+        let mut idx = 0;
+        while idx < chars.len() {
+            let c = chars[idx];
+            let column = idx + 1;
+            if c.is_whitespace() {
+                idx += 1;
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let start = idx;
+                while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '_')
+                {
+                    idx += 1;
+                }
+                tokens.push((Token::Ident(chars[start..idx].iter().collect()), column));
+            } else if c.is_ascii_digit() {
+                let start = idx;
+                while idx < chars.len() && chars[idx].is_ascii_digit() {
+                    idx += 1;
+                }
+                let number = chars[start..idx].iter().collect::<String>();
+                let number = number
+                    .parse()
+                    .map_err(|_| AssemblyError::new(line, column, "The number is too large"))?;
+                tokens.push((Token::Number(number), column));
+            } else if "[]#+-,:=".contains(c) {
+                tokens.push((Token::Punct(c), column));
+                idx += 1;
+            } else {
+                return Err(AssemblyError::new(
+                    line,
+                    column,
+                    format!("Unexpected character '{}'", c),
+                ));
+            }
+        }
+
+        Ok(Self {
+            line,
+            tokens,
+            position: 0,
+            end: chars.len() + 1,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|(token, _)| token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|(_, column)| *column)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self, msg: impl Into<String>) -> AssemblyError {
+        AssemblyError::new(self.line, self.column(), msg)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), AssemblyError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{}'", c)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<(String, usize), AssemblyError> {
+        let column = self.column();
+        match self.next() {
+            Some(Token::Ident(name)) => Ok((name, column)),
+            _ => {
+                self.position -= 1;
+                Err(self.error("Expected a name"))
+            }
+        }
+    }
+
+    /// Parses a label definition (`name:`), if there is one.
+    fn label(&mut self) -> Option<(String, usize)> {
+        match (self.peek(), self.peek_at(1)) {
+            (Some(Token::Ident(name)), Some(Token::Punct(':'))) => {
+                let label = (name.clone(), self.column());
+                self.position += 2;
+                Some(label)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the statement of the line, if there is one.
+    fn statement(&mut self) -> Result<Option<Statement>, AssemblyError> {
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+        let (name, column) = self.ident()?;
+        let statement = match name.as_str() {
+            "data" => Statement::Data(self.list(Self::expr)?),
+            "const" => {
+                let (name, column) = self.ident()?;
+                self.expect('=')?;
+                Statement::Constant {
+                    name,
+                    column,
+                    value: self.expr()?,
+                }
+            }
+            _ => {
+                let opcode = MNEMONICS
+                    .iter()
+                    .find(|mnemonic| mnemonic.name == name)
+                    .ok_or_else(|| {
+                        AssemblyError::new(
+                            self.line,
+                            column,
+                            format!("Unknown mnemonic '{}'", name),
+                        )
+                    })?;
+                let operands = if opcode.params == 0 {
+                    Vec::new()
+                } else {
+                    self.list(|parser| {
+                        let column = parser.column();
+                        Ok((parser.operand()?, column))
+                    })?
+                };
+                if operands.len() != opcode.params {
+                    return Err(AssemblyError::new(
+                        self.line,
+                        column,
+                        format!(
+                            "'{}' takes {} operand(s), but {} were given",
+                            opcode.name,
+                            opcode.params,
+                            operands.len()
+                        ),
+                    ));
+                }
+                if let Some(dst) = opcode.dst {
+                    let (operand, column) = &operands[dst];
+                    if operand.mode == 1 {
+                        return Err(AssemblyError::new(
+                            self.line,
+                            *column,
+                            "Cannot write to an operand in immediate mode",
+                        ));
+                    }
+                }
+                Statement::Instruction {
+                    opcode,
+                    operands: operands.into_iter().map(|(operand, _)| operand).collect(),
+                }
+            }
+        };
+        if self.peek().is_some() {
+            return Err(self.error("Unexpected token at the end of the statement"));
+        }
+        Ok(Some(statement))
+    }
+
+    /// Parses a comma-separated list of items.
+    fn list<T>(
+        &mut self,
+        item: impl Fn(&mut Self) -> Result<T, AssemblyError>,
+    ) -> Result<Vec<T>, AssemblyError> {
+        let mut items = vec![item(self)?];
+        while self.eat(',') {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn operand(&mut self) -> Result<Operand, AssemblyError> {
+        if self.eat('[') {
+            let value = self.expr()?;
+            self.expect(']')?;
+            Ok(Operand { mode: 0, value })
+        } else if self.eat('#') {
+            Ok(Operand {
+                mode: 1,
+                value: self.expr()?,
+            })
+        } else if self.peek() == Some(&Token::Ident(String::from("rb"))) {
+            self.position += 1;
+            let value = match self.peek() {
+                Some(Token::Punct('+')) | Some(Token::Punct('-')) => self.expr()?,
+                _ => Expr {
+                    line: self.line,
+                    column: self.column(),
+                    terms: Vec::new(),
+                },
+            };
+            Ok(Operand { mode: 2, value })
+        } else {
+            Err(self.error("Expected an operand ('[x]', '#x' or 'rb+x')"))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, AssemblyError> {
+        let column = self.column();
+        let mut terms = Vec::new();
+        let mut sign = if self.eat('-') {
+            -1
+        } else {
+            self.eat('+');
+            1
+        };
+        loop {
+            let column = self.column();
+            let term = match self.next() {
+                Some(Token::Number(value)) => Term::Number(value),
+                Some(Token::Ident(name)) => Term::Symbol { name, column },
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("Expected a number or a name"));
+                }
+            };
+            terms.push((sign, term));
+
+            sign = if self.eat('+') {
+                1
+            } else if self.eat('-') {
+                -1
+            } else {
+                break;
+            };
+        }
+        Ok(Expr {
+            line: self.line,
+            column,
+            terms,
+        })
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{
+    Fault, HaltReason, Input, IntcodeComputer, IntcodeError, IterInput, Observer, Output,
+    RunReport, StreamError, Word,
+};
+use std::{
+    collections::VecDeque,
+    future,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// An input stream that can be awaited, used by `IntcodeComputer::run_async`.
+///
+/// Unlike [`Input`], a stream that has no value yet returns `Poll::Pending` instead of
+/// blocking the thread, and wakes the task once a value arrives.
+///
+/// [`Input`]: trait.Input.html
+pub trait AsyncInput<W: Word> {
+    /// Polls for the next value of the input stream.
+    fn poll_get(&mut self, cx: &mut Context) -> Poll<Result<W, StreamError>>;
+}
+
+/// An output stream that can be awaited, used by `IntcodeComputer::run_async`.
+///
+/// Unlike [`Output`], a bounded stream can make the computer wait until it has room for a value.
+///
+/// [`Output`]: trait.Output.html
+pub trait AsyncOutput<W: Word> {
+    /// Polls whether the output stream can take another value.
+    ///
+    /// The default implementation is always ready, as for an unbounded stream.
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<()> {
+        Poll::Ready(())
+    }
+
+    /// Pushes the given value into the output stream, after `poll_ready` returned `Poll::Ready`.
+    fn start_send(&mut self, value: W);
+}
+
+impl<W: Word> AsyncInput<W> for VecDeque<W> {
+    fn poll_get(&mut self, _cx: &mut Context) -> Poll<Result<W, StreamError>> {
+        Poll::Ready(self.get())
+    }
+}
+
+impl<W: Word> AsyncInput<W> for &[W] {
+    fn poll_get(&mut self, _cx: &mut Context) -> Poll<Result<W, StreamError>> {
+        Poll::Ready(self.get())
+    }
+}
+
+impl<W: Word, T: Iterator<Item = W>> AsyncInput<W> for IterInput<T> {
+    fn poll_get(&mut self, _cx: &mut Context) -> Poll<Result<W, StreamError>> {
+        Poll::Ready(self.get())
+    }
+}
+
+impl<W: Word> AsyncOutput<W> for Vec<W> {
+    fn start_send(&mut self, value: W) {
+        self.push(value);
+    }
+}
+
+impl<W: Word, F: FnMut(W)> AsyncOutput<W> for F {
+    fn start_send(&mut self, value: W) {
+        self(value);
+    }
+}
+
+/// The state shared by the two ends of an async channel.
+#[derive(Debug)]
+struct Shared<W> {
+    queue: VecDeque<W>,
+    waker: Option<Waker>,
+    senders: usize,
+}
+
+#[derive(Debug)]
+struct Channel<W> {
+    shared: Mutex<Shared<W>>,
+    available: Condvar,
+}
+
+/// Creates an unbounded channel that connects the output of one computer to the input of another.
+///
+/// The receiving end can be awaited (by `run_async`) as well as blocked on (by `run`),
+/// and the channel is closed once all the senders are dropped.
+///
+/// See [`Executor`] for an example.
+///
+/// [`Executor`]: struct.Executor.html
+pub fn async_channel<W: Word>() -> (AsyncSender<W>, AsyncReceiver<W>) {
+    let channel = Arc::new(Channel {
+        shared: Mutex::new(Shared {
+            queue: VecDeque::new(),
+            waker: None,
+            senders: 1,
+        }),
+        available: Condvar::new(),
+    });
+    (
+        AsyncSender {
+            channel: channel.clone(),
+        },
+        AsyncReceiver { channel },
+    )
+}
+
+/// The sending end of a channel created by [`async_channel`].
+///
+/// [`async_channel`]: fn.async_channel.html
+#[derive(Debug)]
+pub struct AsyncSender<W> {
+    channel: Arc<Channel<W>>,
+}
+
+impl<W> AsyncSender<W> {
+    /// Sends the value to the receiving end, waking it if it waits for a value.
+    pub fn send(&self, value: W) {
+        let mut shared = self.channel.shared.lock().unwrap();
+        shared.queue.push_back(value);
+        // wake the receiver, whether it awaits the value or blocks on it
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+        self.channel.available.notify_one();
+    }
+}
+
+impl<W> Clone for AsyncSender<W> {
+    fn clone(&self) -> Self {
+        self.channel.shared.lock().unwrap().senders += 1;
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<W> Drop for AsyncSender<W> {
+    fn drop(&mut self) {
+        let mut shared = self.channel.shared.lock().unwrap();
+        shared.senders -= 1;
+        if shared.senders == 0 {
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+            self.channel.available.notify_all();
+        }
+    }
+}
+
+impl<W: Word> Output<W> for AsyncSender<W> {
+    fn push(&mut self, value: W) {
+        self.send(value);
+    }
+}
+
+impl<W: Word> AsyncOutput<W> for AsyncSender<W> {
+    fn start_send(&mut self, value: W) {
+        self.send(value);
+    }
+}
+
+/// The receiving end of a channel created by [`async_channel`].
+///
+/// [`async_channel`]: fn.async_channel.html
+#[derive(Debug)]
+pub struct AsyncReceiver<W> {
+    channel: Arc<Channel<W>>,
+}
+
+impl<W> AsyncReceiver<W> {
+    /// Takes the next value from the channel, if there is one.
+    pub fn try_recv(&self) -> Option<W> {
+        self.channel.shared.lock().unwrap().queue.pop_front()
+    }
+}
+
+// helper function to create the error of a channel whose senders are all gone
+fn disconnected() -> StreamError {
+    StreamError {
+        msg: String::from("Sender disconnected from the channel"),
+    }
+}
+
+impl<W: Word> Input<W> for AsyncReceiver<W> {
+    fn get(&mut self) -> Result<W, StreamError> {
+        let mut shared = self.channel.shared.lock().unwrap();
+        loop {
+            if let Some(value) = shared.queue.pop_front() {
+                return Ok(value);
+            }
+            if shared.senders == 0 {
+                return Err(disconnected());
+            }
+            shared = self.channel.available.wait(shared).unwrap();
+        }
+    }
+}
+
+impl<W: Word> AsyncInput<W> for AsyncReceiver<W> {
+    fn poll_get(&mut self, cx: &mut Context) -> Poll<Result<W, StreamError>> {
+        let mut shared = self.channel.shared.lock().unwrap();
+        if let Some(value) = shared.queue.pop_front() {
+            Poll::Ready(Ok(value))
+        } else if shared.senders == 0 {
+            Poll::Ready(Err(disconnected()))
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<W, I, O, B> IntcodeComputer<I, O, W, B>
+where
+    W: Word,
+    I: Input<W> + AsyncInput<W>,
+    O: Output<W> + AsyncOutput<W>,
+    B: Observer<W>,
+{
+    /// Runs the program without consuming the memory, awaiting the streams instead of blocking on them.
+    ///
+    /// Behaves like `run`, but while the program waits for input (or for the output stream
+    /// to be ready) the future is pending, so many computers can run on a single thread.
+    /// The future can be driven by the built-in [`Executor`] or by any other executor.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::collections::VecDeque;
+    ///
+    /// let memory = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+    /// let input: VecDeque<_> = vec![41].into();
+    /// let mut computer = IntcodeComputer::new(memory, input, Vec::new());
+    ///
+    /// let report = block_on(computer.run_async()).unwrap();
+    /// assert_eq!(report.outputs, vec![42]);
+    /// ```
+    ///
+    /// [`Executor`]: struct.Executor.html
+    pub async fn run_async(&mut self) -> Result<RunReport<W>, IntcodeError<W>> {
+        // take a snapshot of the state to restore later
+        let snapshot = self.snapshot();
+        self.deadline = None;
+        let started = self.executed;
+        let result = self.run_async_impl().await;
+        let report = self.report(result, started);
+        self.restore(&snapshot);
+
+        report
+    }
+
+    async fn run_async_impl(&mut self) -> Result<W, IntcodeError<W>> {
+        // run the program, awaiting the streams for input and output
+        loop {
+            match self.run_until_io() {
+                HaltReason::NeedsInput => {
+                    let input = &mut self.input;
+                    match future::poll_fn(|cx| input.poll_get(cx)).await {
+                        Ok(input) => self.push_input(input),
+                        Err(err) => return Err(self.fault_at_pc(Fault::Input(err))),
+                    }
+                }
+                HaltReason::Output(value) => {
+                    let output = &mut self.output;
+                    future::poll_fn(|cx| output.poll_ready(cx)).await;
+                    self.outputs.push(value.clone());
+                    self.output.start_send(value);
+                }
+                HaltReason::Watchpoint(watchpoint) => self.hits.push(watchpoint),
+                HaltReason::LimitExceeded(limit) => {
+                    return Err(self.fault_at_pc(Fault::LimitExceeded(limit)))
+                }
+                HaltReason::Halted => return self.read(0).map_err(|fault| self.fault_at_pc(fault)),
+                HaltReason::Error(err) => return Err(err),
+            }
+        }
+    }
+}
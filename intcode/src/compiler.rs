@@ -0,0 +1,455 @@
+use crate::{
+    Fault, Input, IntcodeComputer, IntcodeError, Memory, Observer, Opcode, Output, ParameterMode,
+    RunReport, Word,
+};
+
+/// A parameter of a compiled instruction: its mode and the location of its raw value.
+///
+/// The raw value is read when the instruction runs, so that the operands
+/// can change (e.g. the noun and the verb) without compiling the program again.
+#[derive(Clone, Copy, Debug)]
+struct Param {
+    mode: ParameterMode,
+    address: usize,
+}
+
+/// A compiled instruction that doesn't affect the control flow.
+#[derive(Clone, Debug)]
+enum Operation {
+    Add(Param, Param, Param),
+    Multiply(Param, Param, Param),
+    Input(Param),
+    Output(Param),
+    LessThan(Param, Param, Param),
+    Equals(Param, Param, Param),
+    AdjustRelativeBase(Param),
+}
+
+/// A compiled instruction, along with its location.
+#[derive(Clone, Debug)]
+struct Op {
+    pc: usize,
+    operation: Operation,
+}
+
+impl Op {
+    /// Returns the location of the instruction that follows this one.
+    fn next(&self) -> usize {
+        match self.operation {
+            Operation::Input(_) | Operation::Output(_) | Operation::AdjustRelativeBase(_) => {
+                self.pc + 2
+            }
+            _ => self.pc + 4,
+        }
+    }
+}
+
+/// How the execution continues after the operations of a [`Block`].
+#[derive(Clone, Debug)]
+enum Exit {
+    /// Continue with the block that starts at the given location.
+    Next(usize),
+    /// A `JumpIfTrue` (`when` is `true`) or `JumpIfFalse` instruction.
+    Jump {
+        pc: usize,
+        cond: Param,
+        target: Param,
+        when: bool,
+    },
+    /// A `Terminate` instruction.
+    Halt(usize),
+    /// The instruction at the given location couldn't be compiled, and is left to the interpreter.
+    Interpret(usize),
+}
+
+/// A basic block: straight-line operations followed by a single exit.
+#[derive(Clone, Debug)]
+struct Block {
+    ops: Vec<Op>,
+    exit: Exit,
+}
+
+/// An Intcode program, compiled ahead of time into basic blocks of pre-decoded operations.
+///
+/// Created by [`compile`], and run with `IntcodeComputer::run_compiled`.
+///
+/// [`compile`]: fn.compile.html
+#[derive(Clone, Debug)]
+pub struct CompiledProgram<W: Word> {
+    blocks: Vec<Block>,
+    block_at: Vec<Option<usize>>,
+    is_code: Vec<bool>,
+    opcodes: Vec<(usize, W)>,
+    len: usize,
+}
+
+impl<W: Word> CompiledProgram<W> {
+    /// Returns the number of basic blocks in the program.
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if the given location holds the opcode of a compiled instruction.
+    ///
+    /// The operands of the instructions are read when they run, so they aren't part of the code.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    ///
+    /// let program = compile(&[1, 0, 0, 0, 99]);
+    /// assert!(program.is_code(0) && program.is_code(4));
+    /// assert!(!program.is_code(1));
+    /// ```
+    pub fn is_code(&self, address: usize) -> bool {
+        self.is_code.get(address).copied().unwrap_or(false)
+    }
+
+    fn block(&self, address: usize) -> Option<&Block> {
+        self.block_at
+            .get(address)
+            .copied()
+            .flatten()
+            .map(|idx| &self.blocks[idx])
+    }
+
+    /// Returns `true` if the code the program was compiled from is unchanged in the given memory.
+    fn matches(&self, memory: &Memory<W>) -> bool {
+        self.opcodes
+            .iter()
+            .all(|(address, opcode)| memory.get(*address).is_ok_and(|value| value == opcode))
+    }
+}
+
+/// Compiles the given memory image into basic blocks.
+///
+/// The code is found by following the control flow from location `0`, including
+/// the targets of jumps whose location is an *immediate* parameter.
+/// Jumps to any other location, and instructions that can't be compiled,
+/// are left to the plain interpreter when (and if) they are reached.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+/// use std::io;
+///
+/// let memory = vec![1101, 40, 2, 0, 99];
+/// let program = compile(&memory);
+/// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+///
+/// assert_eq!(computer.run_compiled(&program).unwrap().result, 42);
+/// ```
+pub fn compile<W: Word>(memory: &[W]) -> CompiledProgram<W> {
+    build(memory.len(), |address| memory.get(address).cloned())
+}
+
+/// Compiles the program whose `len` first words are returned by `read`.
+fn build<W: Word>(len: usize, read: impl Fn(usize) -> Option<W>) -> CompiledProgram<W> {
+    let image = (0..len)
+        .map(|address| read(address).unwrap_or_else(|| W::from_i64(0)))
+        .collect::<Vec<W>>();
+    let mut program = CompiledProgram {
+        blocks: Vec::new(),
+        block_at: vec![None; len],
+        is_code: vec![false; len],
+        opcodes: Vec::new(),
+        len,
+    };
+    let mut pending = vec![0];
+    while let Some(start) = pending.pop() {
+        if start >= len || program.block_at[start].is_some() {
+            continue;
+        }
+        let block = compile_block(&mut program, &image, start, &mut pending);
+        program.block_at[start] = Some(program.blocks.len());
+        program.blocks.push(block);
+    }
+    program
+}
+
+/// Compiles the basic block that starts at the given location,
+/// queueing the locations of the blocks that may follow it.
+fn compile_block<W: Word>(
+    program: &mut CompiledProgram<W>,
+    image: &[W],
+    start: usize,
+    pending: &mut Vec<usize>,
+) -> Block {
+    let mut ops = Vec::new();
+    let mut pc = start;
+    let exit = loop {
+        // stop at the start of a block that was already compiled
+        if pc != start && program.block(pc).is_some() {
+            break Exit::Next(pc);
+        }
+        let (opcode, params) = match decode(image, pc) {
+            Some(decoded) => decoded,
+            None => break Exit::Interpret(pc),
+        };
+        let end = pc + opcode.instruction_size();
+        program.is_code[pc] = true;
+        program.opcodes.push((pc, image[pc].clone()));
+
+        let operation = match opcode {
+            Opcode::JumpIfTrue(_, _) | Opcode::JumpIfFalse(_, _) => {
+                let (cond, target) = (params[0], params[1]);
+                if let ParameterMode::Immediate = target.mode {
+                    if let Some(target) = image[target.address].to_address() {
+                        pending.push(target);
+                    }
+                }
+                pending.push(end);
+                break Exit::Jump {
+                    pc,
+                    cond,
+                    target,
+                    when: matches!(opcode, Opcode::JumpIfTrue(_, _)),
+                };
+            }
+            Opcode::Terminate => break Exit::Halt(pc),
+            _ => match operation(opcode, &params) {
+                Some(operation) => operation,
+                None => break Exit::Interpret(pc),
+            },
+        };
+        ops.push(Op { pc, operation });
+        pc = end;
+    };
+    if let Exit::Next(next) = exit {
+        pending.push(next);
+    }
+    Block { ops, exit }
+}
+
+/// Decodes the instruction at the given location, if it fits in the image.
+fn decode<W: Word>(image: &[W], pc: usize) -> Option<(Opcode, Vec<Param>)> {
+    let opcode = Opcode::from_i64(image.get(pc)?.to_i64()?).ok()?;
+    if pc + opcode.instruction_size() > image.len() {
+        return None;
+    }
+    let params = opcode
+        .parameter_modes()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, mode)| Param {
+            mode,
+            address: pc + 1 + idx,
+        })
+        .collect();
+    Some((opcode, params))
+}
+
+/// Compiles an instruction that doesn't affect the control flow.
+///
+/// Returns `None` for the instructions that do.
+fn operation(opcode: Opcode, params: &[Param]) -> Option<Operation> {
+    Some(match opcode {
+        Opcode::Add(_, _, _) => Operation::Add(params[0], params[1], params[2]),
+        Opcode::Multiply(_, _, _) => Operation::Multiply(params[0], params[1], params[2]),
+        Opcode::Input(_) => Operation::Input(params[0]),
+        Opcode::Output(_) => Operation::Output(params[0]),
+        Opcode::LessThan(_, _, _) => Operation::LessThan(params[0], params[1], params[2]),
+        Opcode::Equals(_, _, _) => Operation::Equals(params[0], params[1], params[2]),
+        Opcode::AdjustRelativeBase(_) => Operation::AdjustRelativeBase(params[0]),
+        Opcode::JumpIfTrue(_, _) | Opcode::JumpIfFalse(_, _) | Opcode::Terminate => return None,
+    })
+}
+
+impl<W: Word, I: Input<W>, O: Output<W>, B: Observer<W>> IntcodeComputer<I, O, W, B> {
+    /// Runs a compiled version of the program, without consuming the memory.
+    ///
+    /// Gives the same results as `run`, but executes the pre-decoded basic blocks of the [`CompiledProgram`].
+    /// Whenever the compiled code can't be used the plain interpreter takes over, namely when:
+    /// * the program writes to the opcode of one of its instructions (self-modification);
+    /// * the program jumps to a location that wasn't compiled;
+    /// * an observer or watchpoints are set.
+    ///
+    /// The operands are read when each instruction runs, so setting them (e.g. the noun and verb)
+    /// doesn't affect the compiled code. If any opcode in memory differs from the one
+    /// the program was compiled from, the memory is compiled again for this run.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::collections::VecDeque;
+    ///
+    /// let memory = vec![1, 0, 0, 0, 99];
+    /// let program = compile(&memory);
+    /// let mut computer = IntcodeComputer::new(memory, VecDeque::new(), Vec::new());
+    ///
+    /// computer.poke(1, 4).unwrap();
+    /// assert_eq!(computer.run_compiled(&program).unwrap().result, 100);
+    ///
+    /// computer.set_limits(Limits {
+    ///     instructions: Some(0),
+    ///     ..Limits::default()
+    /// });
+    /// assert!(computer.run_compiled(&program).is_err());
+    /// ```
+    ///
+    /// [`CompiledProgram`]: struct.CompiledProgram.html
+    pub fn run_compiled(
+        &mut self,
+        program: &CompiledProgram<W>,
+    ) -> Result<RunReport<W>, IntcodeError<W>> {
+        // take a snapshot of the state to restore later
+        let snapshot = self.snapshot();
+        self.deadline = None;
+        let started = self.executed;
+        let result = if program.matches(&self.memory) {
+            self.run_compiled_impl(program)
+        } else {
+            let memory = &self.memory;
+            let program = build(program.len, |address| memory.get(address).ok().cloned());
+            self.run_compiled_impl(&program)
+        };
+        let report = self.report(result, started);
+        self.restore(&snapshot);
+
+        report
+    }
+
+    fn run_compiled_impl(&mut self, program: &CompiledProgram<W>) -> Result<W, IntcodeError<W>> {
+        // the compiled code doesn't notify the observer, nor does it check watchpoints
+        let supported = !B::ENABLED && self.watches.is_empty() && self.triggered.is_none();
+        let mut block = match program.block(self.pc) {
+            Some(block) if supported => block,
+            _ => return self.run_impl(),
+        };
+        loop {
+            for op in &block.ops {
+                self.pc = op.pc;
+                if let Some(limit) = self.check_limits() {
+                    return Err(self.fault_at_pc(Fault::LimitExceeded(limit)));
+                }
+                match self.execute_op(op, program) {
+                    Ok(true) => self.executed += 1,
+                    Ok(false) => {
+                        // the program modified its own code, so it can't be trusted anymore
+                        self.executed += 1;
+                        self.pc = op.next();
+                        return self.run_impl();
+                    }
+                    Err(fault) => return Err(self.fault_at_pc(fault)),
+                }
+            }
+            let next = match &block.exit {
+                Exit::Next(next) => *next,
+                Exit::Jump {
+                    pc,
+                    cond,
+                    target,
+                    when,
+                } => {
+                    self.pc = *pc;
+                    if let Some(limit) = self.check_limits() {
+                        return Err(self.fault_at_pc(Fault::LimitExceeded(limit)));
+                    }
+                    match self.jump(*pc, cond, target, *when) {
+                        Ok(next) => next,
+                        Err(fault) => return Err(self.fault_at_pc(fault)),
+                    }
+                }
+                Exit::Halt(pc) => {
+                    self.pc = *pc;
+                    if let Some(limit) = self.check_limits() {
+                        return Err(self.fault_at_pc(Fault::LimitExceeded(limit)));
+                    }
+                    return self.read(0).map_err(|fault| self.fault_at_pc(fault));
+                }
+                Exit::Interpret(pc) => {
+                    self.pc = *pc;
+                    return self.run_impl();
+                }
+            };
+            block = match program.block(next) {
+                Some(block) => block,
+                None => {
+                    self.pc = next;
+                    return self.run_impl();
+                }
+            };
+        }
+    }
+
+    // helper function to execute a compiled operation
+    // returns `false` if the operation wrote to the program's code
+    fn execute_op(&mut self, op: &Op, program: &CompiledProgram<W>) -> Result<bool, Fault<W>> {
+        // helper function to turn a comparison into a value
+        fn from_bool<W: Word>(value: bool) -> W {
+            W::from_i64(if value { 1 } else { 0 })
+        }
+
+        let (address, value) = match &op.operation {
+            Operation::Add(src1, src2, dst) => {
+                let value = self.load(src1)?.checked_add(&self.load(src2)?);
+                (self.location(dst)?, value.ok_or(Fault::Overflow)?)
+            }
+            Operation::Multiply(src1, src2, dst) => {
+                let value = self.load(src1)?.checked_mul(&self.load(src2)?);
+                (self.location(dst)?, value.ok_or(Fault::Overflow)?)
+            }
+            Operation::Input(dst) => {
+                let address = self.location(dst)?;
+                let input = match self.pending_input.pop_front() {
+                    Some(input) => input,
+                    None => self.input.get().map_err(Fault::Input)?,
+                };
+                (address, input)
+            }
+            Operation::Output(src) => {
+                let value = self.load(src)?;
+                self.outputs.push(value.clone());
+                self.output.push(value);
+                return Ok(true);
+            }
+            Operation::LessThan(src1, src2, dst) => {
+                let value = from_bool(self.load(src1)? < self.load(src2)?);
+                (self.location(dst)?, value)
+            }
+            Operation::Equals(src1, src2, dst) => {
+                let value = from_bool(self.load(src1)? == self.load(src2)?);
+                (self.location(dst)?, value)
+            }
+            Operation::AdjustRelativeBase(src) => {
+                self.relative_base = self
+                    .load(src)?
+                    .to_i64()
+                    .and_then(|offset| self.relative_base.checked_add(offset))
+                    .ok_or(Fault::Overflow)?;
+                return Ok(true);
+            }
+        };
+        self.write(address, value)?;
+        Ok(!program.is_code(address))
+    }
+
+    // helper function to evaluate a compiled jump, returning the location of the next instruction
+    fn jump(
+        &mut self,
+        pc: usize,
+        cond: &Param,
+        target: &Param,
+        when: bool,
+    ) -> Result<usize, Fault<W>> {
+        let cond = self.load(cond)?;
+        let target = self.load(target)?;
+        let next = if cond.is_zero() != when {
+            target.to_address().ok_or(Fault::InvalidAddress(target))?
+        } else {
+            pc + 3
+        };
+        self.executed += 1;
+        Ok(next)
+    }
+
+    // helper function to get the value of a compiled parameter
+    fn load(&mut self, param: &Param) -> Result<W, Fault<W>> {
+        self.value(param.address, param.mode)
+    }
+
+    // helper function to get the memory location a compiled parameter points to
+    fn location(&self, param: &Param) -> Result<usize, Fault<W>> {
+        self.address(param.address, param.mode)
+    }
+}
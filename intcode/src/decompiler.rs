@@ -0,0 +1,1015 @@
+use crate::{analyze, opcode_at, writes, BasicBlock, BlockExit, Opcode, Operand, Word};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// The maximal size of an expression that is forwarded into the statements that read it.
+const MAX_FORWARDED_SIZE: usize = 32;
+
+/// A binary operator of an [`Expr`].
+///
+/// [`Expr`]: enum.Expr.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Multiply,
+    LessThan,
+    GreaterOrEqual,
+    Equals,
+    NotEqual,
+}
+
+impl BinaryOp {
+    fn is_comparison(self) -> bool {
+        !matches!(self, BinaryOp::Add | BinaryOp::Multiply)
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Multiply => "*",
+            BinaryOp::LessThan => "<",
+            BinaryOp::GreaterOrEqual => ">=",
+            BinaryOp::Equals => "==",
+            BinaryOp::NotEqual => "!=",
+        }
+    }
+}
+
+/// An expression of the decompiled pseudocode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<W: Word> {
+    /// A constant value, displayed as `5`.
+    Constant(W),
+    /// The memory cell at the given location, displayed as `v12`.
+    Variable(usize),
+    /// The memory cell at the given offset from the relative base, displayed as `rb[3]`.
+    Relative(W),
+    /// The next input value, displayed as `input()`.
+    Input,
+    /// A binary operation, displayed as `v1 + 2`.
+    Binary(Box<Expr<W>>, BinaryOp, Box<Expr<W>>),
+}
+
+impl<W: Word> Expr<W> {
+    /// Creates a binary operation, folding and reassociating the constants of sums and products.
+    fn binary(lhs: Expr<W>, op: BinaryOp, rhs: Expr<W>) -> Self {
+        use BinaryOp::*;
+        let constant = |expr: &Expr<W>| match expr {
+            Expr::Constant(value) => Some(value.clone()),
+            _ => None,
+        };
+        let fold = |lhs: &W, rhs: &W| match op {
+            Add => lhs.checked_add(rhs),
+            Multiply => lhs.checked_mul(rhs),
+            LessThan => Some(from_bool(lhs < rhs)),
+            GreaterOrEqual => Some(from_bool(lhs >= rhs)),
+            Equals => Some(from_bool(lhs == rhs)),
+            NotEqual => Some(from_bool(lhs != rhs)),
+        };
+
+        match (op, lhs, rhs) {
+            (_, Expr::Constant(lhs), Expr::Constant(rhs)) if fold(&lhs, &rhs).is_some() => {
+                Expr::Constant(fold(&lhs, &rhs).unwrap())
+            }
+            // keep the constant of a sum or a product on the right (two constants that
+            // overflow when folded are kept as they are)
+            (Add, Expr::Constant(lhs), rhs) | (Multiply, Expr::Constant(lhs), rhs)
+                if constant(&rhs).is_none() =>
+            {
+                Expr::binary(rhs, op, Expr::Constant(lhs))
+            }
+            (Add, lhs, Expr::Constant(rhs)) if rhs.is_zero() => lhs,
+            // a comparison is either 0 or 1
+            (NotEqual, Expr::Binary(x, cmp, y), Expr::Constant(zero))
+            | (Equals, Expr::Binary(x, cmp, y), Expr::Constant(zero))
+                if zero.is_zero() && cmp.is_comparison() =>
+            {
+                let comparison = Expr::Binary(x, cmp, y);
+                match op {
+                    NotEqual => comparison,
+                    _ => comparison.negate(),
+                }
+            }
+            (Multiply, lhs, Expr::Constant(rhs)) if rhs == W::from_i64(1) => lhs,
+            // (x + a) + b => x + (a + b), and (x * a) * b => x * (a * b)
+            (Add, Expr::Binary(x, Add, a), Expr::Constant(b))
+            | (Multiply, Expr::Binary(x, Multiply, a), Expr::Constant(b))
+                if constant(&a).and_then(|a| fold(&a, &b)).is_some() =>
+            {
+                let folded = fold(&constant(&a).unwrap(), &b).unwrap();
+                Expr::binary(*x, op, Expr::Constant(folded))
+            }
+            // (x + a) * b => x * b + a * b
+            (Multiply, Expr::Binary(x, Add, a), Expr::Constant(b))
+                if constant(&a).and_then(|a| a.checked_mul(&b)).is_some() =>
+            {
+                let folded = constant(&a).unwrap().checked_mul(&b).unwrap();
+                Expr::binary(
+                    Expr::binary(*x, Multiply, Expr::Constant(b)),
+                    Add,
+                    Expr::Constant(folded),
+                )
+            }
+            // x + (y + a) => (x + y) + a
+            (Add, x, Expr::Binary(y, Add, a)) if constant(&a).is_some() => {
+                Expr::binary(Expr::binary(x, Add, *y), Add, *a)
+            }
+            (op, lhs, rhs) => Expr::Binary(Box::new(lhs), op, Box::new(rhs)),
+        }
+    }
+
+    /// Returns the condition that holds when this one doesn't.
+    fn negate(self) -> Self {
+        match self {
+            Expr::Binary(lhs, op, rhs) => {
+                let negated = match op {
+                    BinaryOp::LessThan => BinaryOp::GreaterOrEqual,
+                    BinaryOp::GreaterOrEqual => BinaryOp::LessThan,
+                    BinaryOp::Equals => BinaryOp::NotEqual,
+                    BinaryOp::NotEqual => BinaryOp::Equals,
+                    _ => {
+                        return Expr::binary(
+                            Expr::Binary(lhs, op, rhs),
+                            BinaryOp::Equals,
+                            Expr::Constant(zero()),
+                        )
+                    }
+                };
+                Expr::binary(*lhs, negated, *rhs)
+            }
+            expr => Expr::binary(expr, BinaryOp::Equals, Expr::Constant(zero())),
+        }
+    }
+
+    /// Returns `true` if the expression reads the given variable.
+    fn reads(&self, variable: usize) -> bool {
+        match self {
+            Expr::Variable(address) => *address == variable,
+            Expr::Binary(lhs, _, rhs) => lhs.reads(variable) || rhs.reads(variable),
+            Expr::Constant(_) | Expr::Relative(_) | Expr::Input => false,
+        }
+    }
+
+    /// Returns the absolute value of a negative constant.
+    fn negative_constant(&self) -> Option<W> {
+        match self {
+            Expr::Constant(value) if *value < zero() => value.checked_mul(&W::from_i64(-1)),
+            _ => None,
+        }
+    }
+
+    fn has_input(&self) -> bool {
+        match self {
+            Expr::Input => true,
+            Expr::Binary(lhs, _, rhs) => lhs.has_input() || rhs.has_input(),
+            Expr::Constant(_) | Expr::Variable(_) | Expr::Relative(_) => false,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Expr::Binary(lhs, _, rhs) => 1 + lhs.size() + rhs.size(),
+            _ => 1,
+        }
+    }
+
+    // helper function to collect the variables the expression reads
+    fn variables(&self, variables: &mut BTreeSet<usize>) {
+        match self {
+            Expr::Variable(address) => {
+                variables.insert(*address);
+            }
+            Expr::Binary(lhs, _, rhs) => {
+                lhs.variables(variables);
+                rhs.variables(variables);
+            }
+            Expr::Constant(_) | Expr::Relative(_) | Expr::Input => {}
+        }
+    }
+}
+
+impl<W: Word> Display for Expr<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // helper function to wrap nested operations in parentheses
+        fn operand<W: Word>(f: &mut Formatter, expr: &Expr<W>) -> fmt::Result {
+            match expr {
+                Expr::Binary(_, _, _) => write!(f, "({})", expr),
+                _ => write!(f, "{}", expr),
+            }
+        }
+
+        match self {
+            Expr::Constant(value) => write!(f, "{}", value),
+            Expr::Variable(address) => write!(f, "v{}", address),
+            Expr::Relative(offset) => write!(f, "rb[{}]", offset),
+            Expr::Input => f.write_str("input()"),
+            // show the addition of a negative constant as a subtraction
+            Expr::Binary(lhs, BinaryOp::Add, rhs) if rhs.negative_constant().is_some() => {
+                operand(f, lhs)?;
+                write!(f, " - {}", rhs.negative_constant().unwrap())
+            }
+            Expr::Binary(lhs, op, rhs) => {
+                operand(f, lhs)?;
+                write!(f, " {} ", op.symbol())?;
+                operand(f, rhs)
+            }
+        }
+    }
+}
+
+/// A statement of the decompiled pseudocode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement<W: Word> {
+    /// Assigns the value (the second expression) to the memory cell (the first expression).
+    Assign(Expr<W>, Expr<W>),
+    /// Outputs the value.
+    Output(Expr<W>),
+    /// Adds the value to the relative base.
+    AdjustRelativeBase(Expr<W>),
+    /// Runs `then` if the condition holds, and `otherwise` if it doesn't.
+    If {
+        condition: Expr<W>,
+        then: Vec<Statement<W>>,
+        otherwise: Vec<Statement<W>>,
+    },
+    /// Runs the body as long as the condition holds, checking it before every iteration.
+    While {
+        condition: Expr<W>,
+        body: Vec<Statement<W>>,
+    },
+    /// Runs the body as long as the condition holds, checking it after every iteration.
+    DoWhile {
+        body: Vec<Statement<W>>,
+        condition: Expr<W>,
+    },
+    /// Leaves the innermost loop.
+    Break,
+    /// Starts the next iteration of the innermost `While` loop.
+    Continue,
+    /// Marks the instruction at the given location as the target of a `Goto`.
+    Label(usize),
+    /// Continues the execution at the given location.
+    Goto(usize),
+    /// Continues the execution at the location given by the value (a dynamic jump).
+    GotoDynamic(Expr<W>),
+    /// Terminates the program, returning the value (the memory cell at location `0`).
+    Return(Expr<W>),
+    /// The instruction at the given location can't be executed.
+    Fault(usize),
+}
+
+impl<W: Word> Statement<W> {
+    fn write(&self, f: &mut Formatter, depth: usize) -> fmt::Result {
+        let indent = "    ".repeat(depth);
+        match self {
+            Statement::Assign(dst, value) => writeln!(f, "{}{} = {};", indent, dst, value),
+            Statement::Output(value) => writeln!(f, "{}output({});", indent, value),
+            Statement::AdjustRelativeBase(value) => writeln!(f, "{}rb += {};", indent, value),
+            Statement::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                writeln!(f, "{}if ({}) {{", indent, condition)?;
+                write_block(f, then, depth + 1)?;
+                if !otherwise.is_empty() {
+                    writeln!(f, "{}}} else {{", indent)?;
+                    write_block(f, otherwise, depth + 1)?;
+                }
+                writeln!(f, "{}}}", indent)
+            }
+            Statement::While { condition, body } => {
+                writeln!(f, "{}while ({}) {{", indent, condition)?;
+                write_block(f, body, depth + 1)?;
+                writeln!(f, "{}}}", indent)
+            }
+            Statement::DoWhile { body, condition } => {
+                writeln!(f, "{}do {{", indent)?;
+                write_block(f, body, depth + 1)?;
+                writeln!(f, "{}}} while ({});", indent, condition)
+            }
+            Statement::Break => writeln!(f, "{}break;", indent),
+            Statement::Continue => writeln!(f, "{}continue;", indent),
+            // labels are outdented, as is customary
+            Statement::Label(address) => {
+                writeln!(f, "{}L{:04}:", "    ".repeat(depth - 1), address)
+            }
+            Statement::Goto(address) => writeln!(f, "{}goto L{:04};", indent, address),
+            Statement::GotoDynamic(target) => writeln!(f, "{}goto *{};", indent, target),
+            Statement::Return(value) => writeln!(f, "{}return {};", indent, value),
+            Statement::Fault(address) => {
+                writeln!(
+                    f,
+                    "{}fault(); // invalid instruction at {}",
+                    indent, address
+                )
+            }
+        }
+    }
+}
+
+fn write_block<W: Word>(
+    f: &mut Formatter,
+    statements: &[Statement<W>],
+    depth: usize,
+) -> fmt::Result {
+    statements
+        .iter()
+        .try_for_each(|statement| statement.write(f, depth))
+}
+
+/// A decompiled Intcode program, displayed as pseudo-C.
+///
+/// Created by [`decompile`].
+///
+/// [`decompile`]: fn.decompile.html
+#[derive(Clone, Debug)]
+pub struct Decompilation<W: Word> {
+    /// The locations of the memory cells that are the parameters of the program.
+    pub parameters: Vec<usize>,
+    /// The other memory cells the program uses, along with their initial values.
+    pub variables: Vec<(usize, W)>,
+    /// The statements of the program.
+    pub body: Vec<Statement<W>>,
+}
+
+impl<W: Word> Display for Decompilation<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (address, value) in &self.variables {
+            writeln!(f, "int v{} = {};", address, value)?;
+        }
+        if !self.variables.is_empty() {
+            writeln!(f)?;
+        }
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|address| format!("int v{}", address))
+            .collect::<Vec<String>>()
+            .join(", ");
+        writeln!(f, "int main({}) {{", parameters)?;
+        write_block(f, &self.body, 1)?;
+        writeln!(f, "}}")
+    }
+}
+
+/// Decompiles the given memory image into structured pseudocode.
+///
+/// The control flow is taken from the program's [`ControlFlowGraph`], and is rebuilt into
+/// `if`, `while` and `do`-`while` statements where possible, falling back to `goto` elsewhere.
+/// Memory cells are named as variables after their location (`v12`), and the value of a variable
+/// is forwarded into the statements of the same block that read it.
+///
+/// The `parameters` are the locations of the cells that are set before the program runs
+/// (e.g. the noun and verb of day 2). Unless the program has dynamic jumps or writes in *relative*
+/// mode, the cells the program never writes to are replaced by their values.
+///
+/// The decompilation describes the program as it is in memory: code that the program
+/// writes over while it runs is decompiled as it was before the run.
+///
+/// # Example
+/// ```
+/// use intcode::decompile;
+///
+/// // reads input values until one of them is at least 10, and outputs it
+/// let memory = vec![3, 12, 1007, 12, 10, 13, 1005, 13, 0, 4, 12, 99, 0, 0];
+/// let pseudocode = decompile(&memory, &[]).to_string();
+///
+/// assert!(pseudocode.contains("do {"));
+/// assert!(pseudocode.contains("v12 = input();"));
+/// assert!(pseudocode.contains("} while (v12 < 10);"));
+/// assert!(pseudocode.contains("output(v12);"));
+/// ```
+///
+/// [`ControlFlowGraph`]: struct.ControlFlowGraph.html
+pub fn decompile<W: Word>(memory: &[W], parameters: &[usize]) -> Decompilation<W> {
+    let analysis = analyze(memory);
+    let mut decompiler = Decompiler {
+        memory,
+        blocks: &analysis.cfg.blocks,
+        parameters,
+        written: BTreeSet::new(),
+        inline_constants: analysis.dynamic_jumps.is_empty(),
+    };
+
+    // find the cells the program writes to, and the cells of its code
+    let mut code = BTreeSet::new();
+    let mut relative = false;
+    for block in decompiler.blocks {
+        if let BlockExit::Fault(address) = block.exit {
+            code.insert(address);
+        }
+        for (pc, instruction) in block.located() {
+            code.extend(pc..pc + instruction.size());
+            relative |= instruction
+                .operands
+                .iter()
+                .any(|param| matches!(param, Operand::Relative(_)));
+            // every reachable instruction has a valid opcode
+            if !writes(opcode_at(memory, pc).unwrap()) {
+                continue;
+            }
+            match instruction.operands.last() {
+                Some(Operand::Position(dst)) => {
+                    if let Some(address) = dst.to_address() {
+                        decompiler.written.insert(address);
+                    }
+                }
+                _ => decompiler.inline_constants = false,
+            }
+        }
+    }
+
+    let mut body = decompiler.region(0, usize::MAX, Context::default());
+    // the values that are never read can only be found if every read is known
+    if !relative && analysis.dynamic_jumps.is_empty() {
+        // writes to the code change the program, and so are always kept
+        while remove_dead_assignments(&mut body, &|address| code.contains(&address)) {}
+    }
+    let mut body = simplify_loops(body);
+    let mut targets = BTreeSet::new();
+    goto_targets(&body, &mut targets);
+    remove_labels(&mut body, &targets);
+
+    let (mut reads, mut assigned) = (BTreeSet::new(), BTreeSet::new());
+    statement_variables(&body, &mut reads, &mut assigned);
+    let variables = reads
+        .union(&assigned)
+        .copied()
+        .filter(|address| !parameters.contains(address))
+        .map(|address| (address, decompiler.initial(address)))
+        .collect();
+
+    Decompilation {
+        parameters: parameters.to_vec(),
+        variables,
+        body,
+    }
+}
+
+/// The innermost loop around the statements that are being decompiled.
+#[derive(Clone, Copy, Debug, Default)]
+struct Context {
+    /// The location that a `Break` continues at.
+    break_at: Option<usize>,
+    /// The location that a `Continue` continues at.
+    continue_at: Option<usize>,
+}
+
+struct Decompiler<'a, W: Word> {
+    memory: &'a [W],
+    blocks: &'a [BasicBlock<W>],
+    parameters: &'a [usize],
+    written: BTreeSet<usize>,
+    inline_constants: bool,
+}
+
+impl<W: Word> Decompiler<'_, W> {
+    /// Decompiles the blocks that start in the locations `from..to`.
+    fn region(&self, from: usize, to: usize, context: Context) -> Vec<Statement<W>> {
+        let mut statements = Vec::new();
+        let mut idx = self.first_block(from);
+        while idx < self.blocks.len() && self.blocks[idx].start < to {
+            let block = &self.blocks[idx];
+            if let Some(last) = self.loop_end(idx, to) {
+                let last_block = &self.blocks[last];
+                statements.push(self.decompile_loop(block.start, last_block));
+                idx = self.first_block(last_block.end());
+                continue;
+            }
+
+            statements.push(Statement::Label(block.start));
+            let (body, jump_values) = self.straight(block);
+            statements.extend(body);
+            let end = block.end();
+            match &block.exit {
+                BlockExit::FallThrough(next) => statements.extend(jump(*next, end, context)),
+                BlockExit::Halt | BlockExit::Fault(_) => {}
+                BlockExit::Branch {
+                    target,
+                    fall_through: None,
+                } => statements.extend(Self::goto(block, *target, &jump_values, context)),
+                BlockExit::Branch {
+                    target,
+                    fall_through: Some(_),
+                } => {
+                    let condition = self.condition(block, &jump_values);
+                    match target {
+                        Some(target) if *target == end => {}
+                        // a forward jump over the following blocks
+                        Some(target)
+                            if *target > end
+                                && *target <= to
+                                && Some(*target) != context.break_at
+                                && Some(*target) != context.continue_at =>
+                        {
+                            let mut then = self.region(end, *target, context);
+                            let mut otherwise = Vec::new();
+                            let mut next = *target;
+                            // the blocks that are jumped to are the else branch,
+                            // if the then branch jumps over them
+                            if let Some(Statement::Goto(after)) = then.last() {
+                                if *after > *target && *after <= to {
+                                    next = *after;
+                                    then.pop();
+                                    otherwise = self.region(*target, next, context);
+                                }
+                            }
+                            statements.push(Statement::If {
+                                condition: condition.negate(),
+                                then,
+                                otherwise,
+                            });
+                            idx = self.first_block(next);
+                            continue;
+                        }
+                        _ => statements.push(Statement::If {
+                            condition,
+                            then: Self::goto(block, *target, &jump_values, context),
+                            otherwise: Vec::new(),
+                        }),
+                    }
+                }
+            }
+            idx += 1;
+        }
+        statements
+    }
+
+    /// Decompiles the loop that starts at `start`, and whose last block (the one
+    /// that jumps back to the start) is `last`.
+    fn decompile_loop(&self, start: usize, last: &BasicBlock<W>) -> Statement<W> {
+        let exit = last.end();
+        match last.exit {
+            // a conditional jump back to the start
+            BlockExit::Branch {
+                fall_through: Some(_),
+                ..
+            } => {
+                let context = Context {
+                    break_at: Some(exit),
+                    continue_at: None,
+                };
+                let mut body = self.region(start, last.start, context);
+                let (statements, jump_values) = self.straight(last);
+                body.push(Statement::Label(last.start));
+                body.extend(statements);
+                Statement::DoWhile {
+                    body,
+                    condition: self.condition(last, &jump_values),
+                }
+            }
+            // an unconditional jump back to the start
+            _ => {
+                let context = Context {
+                    break_at: Some(exit),
+                    continue_at: Some(start),
+                };
+                let mut body = self.region(start, last.start, context);
+                body.push(Statement::Label(last.start));
+                body.extend(self.straight(last).0);
+                Statement::While {
+                    condition: Expr::Constant(W::from_i64(1)),
+                    body,
+                }
+            }
+        }
+    }
+
+    /// Returns the index of the furthest block in the region ending at `to`,
+    /// that jumps back to the start of the block at `idx`.
+    fn loop_end(&self, idx: usize, to: usize) -> Option<usize> {
+        let start = self.blocks[idx].start;
+        (idx..self.blocks.len())
+            .take_while(|last| self.blocks[*last].end() <= to)
+            .filter(|last| match self.blocks[*last].exit {
+                BlockExit::Branch { target, .. } => target == Some(start),
+                _ => false,
+            })
+            .last()
+    }
+
+    /// Returns the index of the first block that starts at or after the given location.
+    fn first_block(&self, address: usize) -> usize {
+        self.blocks.partition_point(|block| block.start < address)
+    }
+
+    /// Decompiles the instructions of the block, except for the jump that ends it.
+    ///
+    /// Returns the statements, and the values of the parameters of the jump (if any).
+    fn straight(&self, block: &BasicBlock<W>) -> (Vec<Statement<W>>, Vec<Option<Expr<W>>>) {
+        let mut statements = Vec::new();
+        // the assignments whose values are forwarded, and that weren't written yet
+        let mut pending: Vec<(usize, Expr<W>)> = Vec::new();
+        for (pc, instruction) in block.located() {
+            let opcode = opcode_at(self.memory, pc).unwrap();
+            let params = &instruction.operands;
+            // the relative base may point anywhere, so every value is written before it is used
+            let relative = params
+                .iter()
+                .any(|param| matches!(param, Operand::Relative(_)));
+            if relative {
+                flush(&mut pending, &mut statements);
+            }
+            // a value that reads the previous value of its variable is only correct until
+            // the variable is written, so it is written before it is read
+            let reads = match opcode {
+                Opcode::Terminate => vec![0],
+                _ if writes(opcode) => params[..params.len() - 1]
+                    .iter()
+                    .filter_map(position)
+                    .collect(),
+                _ => params.iter().filter_map(position).collect(),
+            };
+            for address in reads {
+                let stale = pending
+                    .iter()
+                    .position(|(variable, value)| *variable == address && value.reads(address));
+                if let Some(idx) = stale {
+                    let (variable, value) = pending.remove(idx);
+                    statements.push(Statement::Assign(Expr::Variable(variable), value));
+                }
+            }
+            let src = |idx: usize, pending: &[(usize, Expr<W>)]| self.src(&params[idx], pending);
+            let (value, dst) = match opcode {
+                Opcode::Add(_, _, _) => (BinaryOp::Add, 2),
+                Opcode::Multiply(_, _, _) => (BinaryOp::Multiply, 2),
+                Opcode::LessThan(_, _, _) => (BinaryOp::LessThan, 2),
+                Opcode::Equals(_, _, _) => (BinaryOp::Equals, 2),
+                Opcode::Input(_) => {
+                    match self.dst(&params[0]) {
+                        Some(dst) => assign(&mut pending, &mut statements, dst, Expr::Input, false),
+                        None => return (fault(statements, pending, pc), vec![]),
+                    }
+                    continue;
+                }
+                Opcode::Output(_) => {
+                    match src(0, &pending) {
+                        Some(value) => statements.push(Statement::Output(value)),
+                        None => return (fault(statements, pending, pc), vec![]),
+                    }
+                    continue;
+                }
+                Opcode::AdjustRelativeBase(_) => {
+                    match src(0, &pending) {
+                        Some(value) => statements.push(Statement::AdjustRelativeBase(value)),
+                        None => return (fault(statements, pending, pc), vec![]),
+                    }
+                    continue;
+                }
+                Opcode::JumpIfTrue(_, _) | Opcode::JumpIfFalse(_, _) => {
+                    let values = (0..params.len()).map(|idx| src(idx, &pending)).collect();
+                    flush(&mut pending, &mut statements);
+                    return (statements, values);
+                }
+                Opcode::Terminate => {
+                    // nothing runs after the program terminates, but its return value is read
+                    statements.push(Statement::Return(self.read(0, &pending)));
+                    return (statements, vec![]);
+                }
+            };
+            let operands = (src(0, &pending), src(1, &pending), self.dst(&params[dst]));
+            match operands {
+                (Some(lhs), Some(rhs), Some(dst)) => {
+                    let value = Expr::binary(lhs, value, rhs);
+                    assign(&mut pending, &mut statements, dst, value, !relative);
+                }
+                _ => return (fault(statements, pending, pc), vec![]),
+            }
+        }
+        flush(&mut pending, &mut statements);
+        if let BlockExit::Fault(address) = block.exit {
+            statements.push(Statement::Fault(address));
+        }
+        (statements, vec![])
+    }
+
+    /// Returns the condition under which the jump that ends the block is taken,
+    /// given the values of the jump's parameters.
+    fn condition(&self, block: &BasicBlock<W>, jump: &[Option<Expr<W>>]) -> Expr<W> {
+        let (pc, _) = block.located().last().unwrap();
+        let value = jump[0].clone().unwrap_or_else(|| Expr::Constant(zero()));
+        match opcode_at(self.memory, pc) {
+            Some(Opcode::JumpIfTrue(_, _)) => {
+                Expr::binary(value, BinaryOp::NotEqual, Expr::Constant(zero()))
+            }
+            _ => Expr::binary(value, BinaryOp::Equals, Expr::Constant(zero())),
+        }
+    }
+
+    /// Decompiles the jump that ends the block, to the given target
+    /// (`None` for a dynamic jump, whose location is the second of the `jump_values`).
+    fn goto(
+        block: &BasicBlock<W>,
+        target: Option<usize>,
+        jump_values: &[Option<Expr<W>>],
+        context: Context,
+    ) -> Vec<Statement<W>> {
+        match (target, &jump_values[1]) {
+            (Some(target), _) => jump(target, block.end(), context),
+            (None, Some(location)) => vec![Statement::GotoDynamic(location.clone())],
+            (None, None) => {
+                let (pc, _) = block.located().last().unwrap();
+                vec![Statement::Fault(pc)]
+            }
+        }
+    }
+
+    /// Returns the value of a parameter, or `None` if it isn't a valid location.
+    fn src(&self, param: &Operand<W>, pending: &[(usize, Expr<W>)]) -> Option<Expr<W>> {
+        Some(match param {
+            Operand::Immediate(value) => Expr::Constant(value.clone()),
+            Operand::Relative(offset) => Expr::Relative(offset.clone()),
+            Operand::Position(address) => self.read(address.to_address()?, pending),
+        })
+    }
+
+    /// Returns the value of the memory cell at the given location.
+    fn read(&self, address: usize, pending: &[(usize, Expr<W>)]) -> Expr<W> {
+        match pending.iter().find(|(variable, _)| *variable == address) {
+            Some((_, value)) => value.clone(),
+            None if self.is_constant(address) => Expr::Constant(self.initial(address)),
+            None => Expr::Variable(address),
+        }
+    }
+
+    /// Returns the memory cell a parameter writes to, or `None` if it isn't a valid location.
+    fn dst(&self, param: &Operand<W>) -> Option<Expr<W>> {
+        match param {
+            Operand::Position(address) => address.to_address().map(Expr::Variable),
+            Operand::Relative(offset) => Some(Expr::Relative(offset.clone())),
+            Operand::Immediate(_) => None,
+        }
+    }
+
+    fn is_constant(&self, address: usize) -> bool {
+        self.inline_constants
+            && !self.written.contains(&address)
+            && !self.parameters.contains(&address)
+    }
+
+    fn initial(&self, address: usize) -> W {
+        self.memory.get(address).cloned().unwrap_or_else(zero)
+    }
+}
+
+/// Returns the statement that continues the execution at `target`,
+/// from the end of a block that is followed by the location `next`.
+fn jump<W: Word>(target: usize, next: usize, context: Context) -> Vec<Statement<W>> {
+    if target == next {
+        vec![]
+    } else if Some(target) == context.break_at {
+        vec![Statement::Break]
+    } else if Some(target) == context.continue_at {
+        vec![Statement::Continue]
+    } else {
+        vec![Statement::Goto(target)]
+    }
+}
+
+/// Assigns a value to a memory cell, forwarding it to the statements that read it if possible.
+fn assign<W: Word>(
+    pending: &mut Vec<(usize, Expr<W>)>,
+    statements: &mut Vec<Statement<W>>,
+    dst: Expr<W>,
+    value: Expr<W>,
+    forward: bool,
+) {
+    if let Expr::Variable(variable) = dst {
+        // the values that read the variable must be written before it changes
+        let (stale, rest): (Vec<_>, Vec<_>) = pending
+            .drain(..)
+            .filter(|(pending, _)| *pending != variable)
+            .partition(|(_, value)| value.reads(variable));
+        statements.extend(
+            stale
+                .into_iter()
+                .map(|(variable, value)| Statement::Assign(Expr::Variable(variable), value)),
+        );
+        *pending = rest;
+        if forward && value.size() <= MAX_FORWARDED_SIZE {
+            pending.push((variable, value));
+            return;
+        }
+    }
+    statements.push(Statement::Assign(dst, value));
+}
+
+/// Writes all the pending assignments.
+fn flush<W: Word>(pending: &mut Vec<(usize, Expr<W>)>, statements: &mut Vec<Statement<W>>) {
+    statements.extend(
+        pending
+            .drain(..)
+            .map(|(variable, value)| Statement::Assign(Expr::Variable(variable), value)),
+    );
+}
+
+// helper function to end a block at an instruction that can't be executed
+fn fault<W: Word>(
+    mut statements: Vec<Statement<W>>,
+    mut pending: Vec<(usize, Expr<W>)>,
+    pc: usize,
+) -> Vec<Statement<W>> {
+    flush(&mut pending, &mut statements);
+    statements.push(Statement::Fault(pc));
+    statements
+}
+
+/// Removes the assignments to the variables that are never read, except for the
+/// variables that must be kept and the assignments of input values.
+///
+/// Returns `true` if an assignment was removed.
+fn remove_dead_assignments<W: Word>(
+    statements: &mut Vec<Statement<W>>,
+    keep: &dyn Fn(usize) -> bool,
+) -> bool {
+    // helper function to remove the dead assignments of the given statements
+    fn remove<W: Word>(statements: &mut Vec<Statement<W>>, dead: &dyn Fn(usize) -> bool) -> bool {
+        let count = statements.len();
+        statements.retain(|statement| match statement {
+            Statement::Assign(Expr::Variable(variable), value) => {
+                !dead(*variable) || value.has_input()
+            }
+            _ => true,
+        });
+        let mut removed = statements.len() != count;
+        for statement in statements {
+            match statement {
+                Statement::If {
+                    then, otherwise, ..
+                } => {
+                    removed |= remove(then, dead);
+                    removed |= remove(otherwise, dead);
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    removed |= remove(body, dead)
+                }
+                _ => {}
+            }
+        }
+        removed
+    }
+
+    let (mut reads, mut assigned) = (BTreeSet::new(), BTreeSet::new());
+    statement_variables(statements, &mut reads, &mut assigned);
+    remove(statements, &|variable| {
+        !reads.contains(&variable) && !keep(variable)
+    })
+}
+
+/// Turns the loops that start by checking whether to leave them into `while` loops.
+fn simplify_loops<W: Word>(statements: Vec<Statement<W>>) -> Vec<Statement<W>> {
+    let mut simplified = Vec::new();
+    for statement in statements {
+        match statement {
+            Statement::While { condition, body } => {
+                let mut body = simplify_loops(body);
+                let labels = body
+                    .iter()
+                    .take_while(|statement| matches!(statement, Statement::Label(_)))
+                    .count();
+                let leave = match (&condition, body.get(labels)) {
+                    (
+                        Expr::Constant(value),
+                        Some(Statement::If {
+                            condition: leave,
+                            then,
+                            otherwise,
+                        }),
+                    ) if !value.is_zero()
+                        && *then == vec![Statement::Break]
+                        && otherwise.is_empty() =>
+                    {
+                        Some(leave.clone())
+                    }
+                    _ => None,
+                };
+                match leave {
+                    Some(leave) => {
+                        body.remove(labels);
+                        // the labels are moved out, so that jumping to them still checks the condition
+                        simplified.extend(body.drain(..labels));
+                        simplified.push(Statement::While {
+                            condition: leave.negate(),
+                            body,
+                        });
+                    }
+                    None => simplified.push(Statement::While { condition, body }),
+                }
+            }
+            Statement::DoWhile { body, condition } => simplified.push(Statement::DoWhile {
+                body: simplify_loops(body),
+                condition,
+            }),
+            Statement::If {
+                condition,
+                then,
+                otherwise,
+            } => simplified.push(Statement::If {
+                condition,
+                then: simplify_loops(then),
+                otherwise: simplify_loops(otherwise),
+            }),
+            statement => simplified.push(statement),
+        }
+    }
+    simplified
+}
+
+fn goto_targets<W: Word>(statements: &[Statement<W>], targets: &mut BTreeSet<usize>) {
+    for statement in statements {
+        match statement {
+            Statement::Goto(target) => {
+                targets.insert(*target);
+            }
+            Statement::If {
+                then, otherwise, ..
+            } => {
+                goto_targets(then, targets);
+                goto_targets(otherwise, targets);
+            }
+            Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                goto_targets(body, targets)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes the labels that no `Goto` refers to.
+fn remove_labels<W: Word>(statements: &mut Vec<Statement<W>>, targets: &BTreeSet<usize>) {
+    statements.retain(|statement| match statement {
+        Statement::Label(address) => targets.contains(address),
+        _ => true,
+    });
+    for statement in statements {
+        match statement {
+            Statement::If {
+                then, otherwise, ..
+            } => {
+                remove_labels(then, targets);
+                remove_labels(otherwise, targets);
+            }
+            Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                remove_labels(body, targets)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the variables the statements read, and the variables they assign to.
+fn statement_variables<W: Word>(
+    statements: &[Statement<W>],
+    variables: &mut BTreeSet<usize>,
+    assigned: &mut BTreeSet<usize>,
+) {
+    for statement in statements {
+        match statement {
+            Statement::Assign(dst, value) => {
+                dst.variables(assigned);
+                value.variables(variables);
+            }
+            Statement::Output(value)
+            | Statement::AdjustRelativeBase(value)
+            | Statement::GotoDynamic(value)
+            | Statement::Return(value) => value.variables(variables),
+            Statement::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                condition.variables(variables);
+                statement_variables(then, variables, assigned);
+                statement_variables(otherwise, variables, assigned);
+            }
+            Statement::While { condition, body } | Statement::DoWhile { body, condition } => {
+                condition.variables(variables);
+                statement_variables(body, variables, assigned);
+            }
+            Statement::Break
+            | Statement::Continue
+            | Statement::Label(_)
+            | Statement::Goto(_)
+            | Statement::Fault(_) => {}
+        }
+    }
+}
+
+// helper function to get the location of a parameter in position mode
+fn position<W: Word>(param: &Operand<W>) -> Option<usize> {
+    match param {
+        Operand::Position(address) => address.to_address(),
+        _ => None,
+    }
+}
+
+fn from_bool<W: Word>(value: bool) -> W {
+    W::from_i64(if value { 1 } else { 0 })
+}
+
+fn zero<W: Word>() -> W {
+    W::from_i64(0)
+}
@@ -0,0 +1,290 @@
+use crate::{Opcode, ParameterMode, Word};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// The maximal number of data words shown on a single line of a listing.
+const DATA_WORDS_PER_LINE: usize = 8;
+
+/// A decoded parameter of an [`Instruction`].
+///
+/// [`Instruction`]: struct.Instruction.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand<W: Word> {
+    /// A parameter in *position* mode, displayed as `[12]`.
+    Position(W),
+    /// A parameter in *immediate* mode, displayed as `#5`.
+    Immediate(W),
+    /// A parameter in *relative* mode, displayed as `rb+3`.
+    Relative(W),
+}
+
+impl<W: Word> Operand<W> {
+    fn new(mode: ParameterMode, value: W) -> Self {
+        match mode {
+            ParameterMode::Position => Operand::Position(value),
+            ParameterMode::Immediate => Operand::Immediate(value),
+            ParameterMode::Relative => Operand::Relative(value),
+        }
+    }
+
+    /// Returns the raw value of the parameter.
+    pub fn value(&self) -> &W {
+        match self {
+            Operand::Position(value) | Operand::Immediate(value) | Operand::Relative(value) => {
+                value
+            }
+        }
+    }
+}
+
+impl<W: Word> Display for Operand<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Operand::Position(value) => write!(f, "[{}]", value),
+            Operand::Immediate(value) => write!(f, "#{}", value),
+            Operand::Relative(value) if *value < W::from_i64(0) => write!(f, "rb{}", value),
+            Operand::Relative(value) => write!(f, "rb+{}", value),
+        }
+    }
+}
+
+/// A decoded instruction.
+///
+/// # Example
+/// ```
+/// use intcode::Instruction;
+///
+/// let instruction = Instruction::decode(&[1002, 4, 3, 4]).unwrap();
+/// assert_eq!(instruction.to_string(), "mul [4], #3, [4]");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction<W: Word> {
+    /// The mnemonic of the instruction's opcode (e.g. `add`).
+    pub mnemonic: &'static str,
+    /// The decoded parameters of the instruction.
+    pub operands: Vec<Operand<W>>,
+}
+
+impl<W: Word> Instruction<W> {
+    /// Decodes the instruction at the start of the given words.
+    ///
+    /// Returns `None` if the words do not start with a valid instruction.
+    /// Missing parameters (past the end of `words`) are decoded as zero.
+    pub fn decode(words: &[W]) -> Option<Self> {
+        let opcode = decode_opcode(words.first()?)?;
+        let param = |idx: usize| words.get(idx).cloned().unwrap_or_else(|| W::from_i64(0));
+        let operands = opcode
+            .parameter_modes()
+            .iter()
+            .enumerate()
+            .map(|(idx, mode)| Operand::new(*mode, param(idx + 1)))
+            .collect();
+        Some(Self {
+            mnemonic: opcode.mnemonic(),
+            operands,
+        })
+    }
+
+    /// Returns the number of words the instruction occupies.
+    pub fn size(&self) -> usize {
+        self.operands.len() + 1
+    }
+}
+
+impl<W: Word> Display for Instruction<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.mnemonic)?;
+        for (idx, operand) in self.operands.iter().enumerate() {
+            let separator = if idx == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, operand)?;
+        }
+        Ok(())
+    }
+}
+
+/// The content of a single line of a [`Disassembly`].
+///
+/// [`Disassembly`]: struct.Disassembly.html
+#[derive(Clone, Debug)]
+pub enum LineContent<W: Word> {
+    /// The words were decoded as an instruction.
+    Code(Instruction<W>),
+    /// The words are not reached as code.
+    Data,
+}
+
+/// A single line of a [`Disassembly`].
+///
+/// [`Disassembly`]: struct.Disassembly.html
+#[derive(Clone, Debug)]
+pub struct Line<W: Word> {
+    /// The memory location of the first word of the line.
+    pub address: usize,
+    /// The raw words of the line.
+    pub words: Vec<W>,
+    /// The decoded content of the line.
+    pub content: LineContent<W>,
+}
+
+impl<W: Word> Display for Line<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let words = self
+            .words
+            .iter()
+            .map(|word| word.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{:04}  {:<24}  ", self.address, words.join(" "))?;
+        match &self.content {
+            LineContent::Code(instruction) => write!(f, "{}", instruction),
+            LineContent::Data => write!(f, "data {}", words.join(", ")),
+        }
+    }
+}
+
+/// A symbolic listing of an Intcode program.
+///
+/// Created by [`disassemble`].
+///
+/// [`disassemble`]: fn.disassemble.html
+#[derive(Clone, Debug)]
+pub struct Disassembly<W: Word> {
+    /// The lines of the listing, ordered by their memory location.
+    pub lines: Vec<Line<W>>,
+}
+
+impl<W: Word> Display for Disassembly<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Disassembles the given memory image into a symbolic listing.
+///
+/// The code is found by following the program's control flow from location `0`,
+/// including the targets of jumps whose location is an *immediate* parameter.
+/// Any locations that are left are listed as data, in the syntax of the assembler, even if
+/// they could be decoded: they can only be reached by computed jumps or self-modified code.
+///
+/// # Example
+/// ```
+/// use intcode::disassemble;
+///
+/// let disassembly = disassemble(&[1105, 1, 4, 42, 104, 3, 99]);
+/// let listing = disassembly
+///     .lines
+///     .iter()
+///     .map(|line| line.to_string())
+///     .collect::<Vec<String>>();
+///
+/// assert!(listing[0].ends_with("jt #1, #4"));
+/// assert!(listing[1].ends_with("data 42"));
+/// assert!(listing[2].ends_with("out #3"));
+/// assert!(listing[3].ends_with("hlt"));
+///
+/// // words after the end of the program are data, even if they look like instructions
+/// let disassembly = disassemble(&[99, 1, 2, 3]);
+/// assert_eq!(disassembly.lines.len(), 2);
+/// assert!(disassembly.lines[1].to_string().ends_with("data 1, 2, 3"));
+/// ```
+pub fn disassemble<W: Word>(memory: &[W]) -> Disassembly<W> {
+    // find the starting location of every instruction
+    let mut code = vec![false; memory.len()];
+    let mut starts = BTreeSet::new();
+    trace(memory, 0, &mut code, &mut starts);
+
+    // build the listing
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < memory.len() {
+        if starts.contains(&address) {
+            let instruction = decode_at(memory, address).unwrap();
+            let end = address + instruction.size();
+            lines.push(Line {
+                address,
+                words: memory[address..end].to_vec(),
+                content: LineContent::Code(instruction),
+            });
+            address = end;
+        } else {
+            // group the data words until the next instruction
+            let mut end = address + 1;
+            while end < memory.len() && !code[end] && end - address < DATA_WORDS_PER_LINE {
+                end += 1;
+            }
+            lines.push(Line {
+                address,
+                words: memory[address..end].to_vec(),
+                content: LineContent::Data,
+            });
+            address = end;
+        }
+    }
+
+    Disassembly { lines }
+}
+
+/// Follows the control flow starting at `address`, marking every
+/// word that belongs to a decoded instruction as code.
+fn trace<W: Word>(memory: &[W], address: usize, code: &mut [bool], starts: &mut BTreeSet<usize>) {
+    let mut pending = vec![address];
+    while let Some(address) = pending.pop() {
+        if address >= memory.len() || code[address] {
+            continue;
+        }
+        let opcode = match decode_opcode(&memory[address]) {
+            Some(opcode) => opcode,
+            None => continue,
+        };
+
+        // an instruction can't overlap an instruction that was already decoded
+        let end = address + opcode.instruction_size();
+        if end > memory.len() || code[address..end].iter().any(|is_code| *is_code) {
+            continue;
+        }
+        code[address..end]
+            .iter_mut()
+            .for_each(|is_code| *is_code = true);
+        starts.insert(address);
+
+        let param = |idx: usize| memory.get(address + idx);
+        match opcode {
+            Opcode::Terminate => {}
+            Opcode::JumpIfTrue(cond_mode, loc_mode) | Opcode::JumpIfFalse(cond_mode, loc_mode) => {
+                // follow the jump target if it is known statically
+                if let (ParameterMode::Immediate, Some(loc)) = (loc_mode, param(2)) {
+                    if let Some(loc) = loc.to_address() {
+                        pending.push(loc);
+                    }
+                }
+
+                // an immediate condition that always jumps has no fall through
+                let always_jumps = match (cond_mode, param(1)) {
+                    (ParameterMode::Immediate, Some(cond)) => match opcode {
+                        Opcode::JumpIfTrue(_, _) => !cond.is_zero(),
+                        _ => cond.is_zero(),
+                    },
+                    _ => false,
+                };
+                if !always_jumps {
+                    pending.push(end);
+                }
+            }
+            _ => pending.push(end),
+        }
+    }
+}
+
+fn decode_opcode<W: Word>(word: &W) -> Option<Opcode> {
+    word.to_i64().and_then(|i| Opcode::from_i64(i).ok())
+}
+
+/// Decodes the instruction at the given location, if it fits entirely in memory.
+fn decode_at<W: Word>(memory: &[W], address: usize) -> Option<Instruction<W>> {
+    Instruction::decode(&memory[address..])
+        .filter(|instruction| address + instruction.size() <= memory.len())
+}
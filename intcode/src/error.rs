@@ -0,0 +1,168 @@
+use crate::{DecodeError, LimitExceeded, MemoryLimitExceeded, StreamError, Word};
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error which can occour while running a program on the [`IntcodeComputer`].
+///
+/// Each error carries the program counter (`pc`) and the raw instruction
+/// that was being executed when the error occurred.
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Debug)]
+pub enum IntcodeError<W: Word> {
+    /// The opcode of the instruction is unknown.
+    UnknownOpcode { pc: usize, instruction: W },
+    /// One of the parameter modes of the instruction is unknown.
+    InvalidParameterMode {
+        pc: usize,
+        instruction: W,
+        mode: i64,
+    },
+    /// A parameter pointed to an invalid (e.g. negative) memory location.
+    InvalidAddress {
+        pc: usize,
+        instruction: W,
+        address: W,
+    },
+    /// A parameter that the instruction writes to was in *immediate* mode.
+    ImmediateWrite { pc: usize, instruction: W },
+    /// An arithmetic operation overflowed the word type.
+    Overflow { pc: usize, instruction: W },
+    /// One of the computer's limits (e.g. its memory limit) was exceeded.
+    LimitExceeded {
+        pc: usize,
+        instruction: W,
+        source: LimitExceeded,
+    },
+    /// The input stream failed to provide a value.
+    Input {
+        pc: usize,
+        instruction: W,
+        source: StreamError,
+    },
+}
+
+impl<W: Word> IntcodeError<W> {
+    /// Returns the program counter at which the error occurred.
+    pub fn pc(&self) -> usize {
+        use IntcodeError::*;
+        match self {
+            UnknownOpcode { pc, .. }
+            | InvalidParameterMode { pc, .. }
+            | InvalidAddress { pc, .. }
+            | ImmediateWrite { pc, .. }
+            | Overflow { pc, .. }
+            | LimitExceeded { pc, .. }
+            | Input { pc, .. } => *pc,
+        }
+    }
+
+    /// Returns the raw instruction that was being executed when the error occurred.
+    pub fn instruction(&self) -> &W {
+        use IntcodeError::*;
+        match self {
+            UnknownOpcode { instruction, .. }
+            | InvalidParameterMode { instruction, .. }
+            | InvalidAddress { instruction, .. }
+            | ImmediateWrite { instruction, .. }
+            | Overflow { instruction, .. }
+            | LimitExceeded { instruction, .. }
+            | Input { instruction, .. } => instruction,
+        }
+    }
+}
+
+impl<W: Word> Display for IntcodeError<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use IntcodeError::*;
+        match self {
+            UnknownOpcode { .. } => write!(f, "Unknown opcode")?,
+            InvalidParameterMode { mode, .. } => write!(f, "Invalid parameter mode {}", mode)?,
+            InvalidAddress { address, .. } => write!(f, "Invalid memory location {}", address)?,
+            ImmediateWrite { .. } => write!(f, "Cannot write to a parameter in immediate mode")?,
+            Overflow { .. } => write!(f, "Arithmetic overflow")?,
+            LimitExceeded { source, .. } => write!(f, "{}", source)?,
+            Input { source, .. } => write!(f, "Failed to get input: {}", source)?,
+        }
+        write!(
+            f,
+            " (pc: {}, instruction: {})",
+            self.pc(),
+            self.instruction()
+        )
+    }
+}
+
+impl<W: Word> error::Error for IntcodeError<W> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            IntcodeError::LimitExceeded { source, .. } => Some(source),
+            IntcodeError::Input { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The cause of an [`IntcodeError`], before the program counter and instruction are known.
+///
+/// [`IntcodeError`]: enum.IntcodeError.html
+#[derive(Debug)]
+pub(crate) enum Fault<W: Word> {
+    UnknownOpcode,
+    InvalidParameterMode(i64),
+    InvalidAddress(W),
+    ImmediateWrite,
+    Overflow,
+    LimitExceeded(LimitExceeded),
+    Input(StreamError),
+}
+
+impl<W: Word> Fault<W> {
+    /// Turns the fault into an [`IntcodeError`] that occurred at the given instruction.
+    ///
+    /// [`IntcodeError`]: enum.IntcodeError.html
+    pub(crate) fn at(self, pc: usize, instruction: W) -> IntcodeError<W> {
+        match self {
+            Fault::UnknownOpcode => IntcodeError::UnknownOpcode { pc, instruction },
+            Fault::InvalidParameterMode(mode) => IntcodeError::InvalidParameterMode {
+                pc,
+                instruction,
+                mode,
+            },
+            Fault::InvalidAddress(address) => IntcodeError::InvalidAddress {
+                pc,
+                instruction,
+                address,
+            },
+            Fault::ImmediateWrite => IntcodeError::ImmediateWrite { pc, instruction },
+            Fault::Overflow => IntcodeError::Overflow { pc, instruction },
+            Fault::LimitExceeded(source) => IntcodeError::LimitExceeded {
+                pc,
+                instruction,
+                source,
+            },
+            Fault::Input(source) => IntcodeError::Input {
+                pc,
+                instruction,
+                source,
+            },
+        }
+    }
+}
+
+impl<W: Word> From<MemoryLimitExceeded> for Fault<W> {
+    fn from(err: MemoryLimitExceeded) -> Self {
+        Fault::LimitExceeded(LimitExceeded::Memory(err))
+    }
+}
+
+impl<W: Word> From<DecodeError> for Fault<W> {
+    fn from(err: DecodeError) -> Self {
+        match err {
+            DecodeError::UnknownOpcode => Fault::UnknownOpcode,
+            DecodeError::InvalidParameterMode(mode) => Fault::InvalidParameterMode(mode),
+        }
+    }
+}
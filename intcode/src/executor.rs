@@ -0,0 +1,168 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// A task of an [`Executor`], whose future is dropped once it finished.
+type Slot<'a> = Option<Pin<Box<dyn Future<Output = ()> + 'a>>>;
+
+/// A minimal single-threaded executor, used to run many computers with `run_async` without threads.
+///
+/// Tasks are spawned with `spawn` and polled by `run` whenever they are woken,
+/// until every task either finished or waits for a value that will never come.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// // the first computer doubles its input, the second one adds 1 to it
+/// let (input, double_in) = async_channel();
+/// let (double_out, add_in) = async_channel();
+/// let (add_out, output) = async_channel();
+/// let double = vec![3, 9, 102, 2, 9, 9, 4, 9, 99, 0];
+/// let add = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+/// let mut double = IntcodeComputer::new(double, double_in, double_out);
+/// let mut add = IntcodeComputer::new(add, add_in, add_out);
+///
+/// let mut executor = Executor::new();
+/// let add = executor.spawn(async move { add.run_async().await });
+/// let double = executor.spawn(async move { double.run_async().await });
+/// assert_eq!(executor.run(), 2); // both wait for input
+///
+/// input.send(20);
+/// assert_eq!(executor.run(), 0);
+/// assert!(double.take().unwrap().is_ok());
+/// assert!(add.take().unwrap().is_ok());
+/// assert_eq!(output.try_recv(), Some(41));
+/// ```
+pub struct Executor<'a> {
+    tasks: Vec<Slot<'a>>,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl<'a> Executor<'a> {
+    /// Creates an executor without any tasks.
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            ready: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Spawns a task that runs the given future, returning a handle to its result.
+    ///
+    /// The task is first polled by the next call to `run`.
+    pub fn spawn<F>(&mut self, future: F) -> Task<F::Output>
+    where
+        F: Future + 'a,
+        F::Output: 'a,
+    {
+        let result = Rc::new(RefCell::new(None));
+        let slot = result.clone();
+        self.tasks.push(Some(Box::pin(async move {
+            let output = future.await;
+            *slot.borrow_mut() = Some(output);
+        })));
+        self.ready.lock().unwrap().push_back(self.tasks.len() - 1);
+
+        Task { result }
+    }
+
+    /// Polls the woken tasks until none is left, returning the number of unfinished tasks.
+    ///
+    /// The unfinished tasks wait to be woken, e.g. by a value sent to a channel,
+    /// after which `run` can be called again.
+    pub fn run(&mut self) -> usize {
+        loop {
+            let id = match self.ready.lock().unwrap().pop_front() {
+                Some(id) => id,
+                None => break,
+            };
+            // a task may be woken several times, or after it finished
+            let task = match &mut self.tasks[id] {
+                Some(task) => task,
+                None => continue,
+            };
+            let waker = Waker::from(Arc::new(TaskWaker {
+                id,
+                ready: self.ready.clone(),
+            }));
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks[id] = None;
+            }
+        }
+        self.tasks.iter().filter(|task| task.is_some()).count()
+    }
+}
+
+impl Default for Executor<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle to the result of a task spawned on an [`Executor`].
+///
+/// [`Executor`]: struct.Executor.html
+#[derive(Debug)]
+pub struct Task<T> {
+    result: Rc<RefCell<Option<T>>>,
+}
+
+impl<T> Task<T> {
+    /// Returns `true` if the task finished and its result was not taken yet.
+    pub fn is_finished(&self) -> bool {
+        self.result.borrow().is_some()
+    }
+
+    /// Takes the result of the task, or returns `None` if it did not finish yet.
+    pub fn take(&self) -> Option<T> {
+        self.result.borrow_mut().take()
+    }
+}
+
+/// Wakes a task of an [`Executor`] by queueing it to be polled.
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.id);
+    }
+}
+
+/// Wakes the thread that blocks on a future in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs the given future to completion on the current thread, blocking until it is done.
+///
+/// See `IntcodeComputer::run_async` for an example.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        thread::park();
+    }
+}
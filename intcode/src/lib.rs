@@ -1,11 +1,61 @@
+mod analysis;
+mod ascii;
+mod assembler;
+mod async_io;
+mod compiler;
+mod decompiler;
+mod disassembler;
+mod error;
+mod executor;
+mod limits;
+mod memory;
+mod network;
+mod observer;
+mod patch;
+mod search;
+mod snapshot;
+mod solver;
+mod symbolic;
+mod topology;
+mod watchpoint;
+mod word;
+
 use std::{
-    error,
+    collections::VecDeque,
     fmt::{self, Display, Formatter},
     io::{self, Stdin, Stdout, Write},
+    mem,
     num::ParseIntError,
+    ops::Range,
     sync::mpsc::{self, Receiver, Sender},
+    time::Instant,
 };
 
+pub use analysis::*;
+pub use ascii::*;
+pub use assembler::*;
+pub use async_io::*;
+pub use compiler::*;
+pub use decompiler::*;
+pub use disassembler::*;
+pub use error::*;
+pub use executor::*;
+pub use limits::*;
+pub use memory::*;
+pub use network::*;
+pub use observer::*;
+pub use patch::*;
+pub use search::*;
+pub use snapshot::*;
+pub use solver::*;
+pub use symbolic::*;
+pub use topology::*;
+pub use watchpoint::*;
+pub use word::*;
+
+/// The number of instructions executed between checks of the time limit.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
 /// The parameter modes support by each `OpCode`.
 ///
 /// Each parameter mode signals to the `IntcodeComputer` how to   
@@ -23,14 +73,22 @@ enum ParameterMode {
     /// # Example
     /// [`4`] - the value of the parameter will be interpreted as simply 4.
     Immediate,
+    /// In this mode, the value of the parameter will be interpreted
+    /// as a memory location, relative to the computer's *relative base*.
+    ///
+    /// # Example
+    /// [`-3`] - with a relative base of 10, the value of the parameter will be interpreted  
+    /// as the value of memory location 7.
+    Relative,
 }
 
 impl ParameterMode {
-    fn from_i32(i: i32) -> Result<Self, ()> {
+    fn from_i64(i: i64) -> Result<Self, DecodeError> {
         match i {
             0 => Ok(ParameterMode::Position),
             1 => Ok(ParameterMode::Immediate),
-            _ => Err(()),
+            2 => Ok(ParameterMode::Relative),
+            _ => Err(DecodeError::InvalidParameterMode(i)),
         }
     }
 }
@@ -38,22 +96,24 @@ impl ParameterMode {
 /// The opcodes supported by the `IntcodeComputer`.
 ///
 /// # Parameter modes
-/// The parameters of each code support two modes:
+/// The parameters of each code support three modes:
 ///
 /// 1. *position* (`0`) - the parameter is interpreted as a **memory location**.
 /// 2. *immediate* (`1`) - the parameter is intereted as a **value**.
+/// 3. *relative* (`2`) - the parameter is interpreted as a **memory location**,  
+///    offset by the computer's *relative base*.
 ///
 /// If the parameter mode is **not** specified for a specific parameter,  
 /// it defaults to *position* (`0`) mode.   
 /// Parameters that the instruction writes to (`dst`) will **never**   
-/// be in *immediate* (`1`) mode, **only** in *position* (`0`) mode.
+/// be in *immediate* (`1`) mode, **only** in *position* (`0`) or *relative* (`2`) mode.
 ///
 /// Parameter modes are stored in the same value as the instruction's opcode.  
 /// The opcode is the rightmost two digits of the first value in an instruction.  
 /// Parameter modes are single digits, one per parameter, read right-to-left from the opcode.
 ///
 /// ## Example
-/// ```
+/// ```text
 /// [1002, 4, 3, 4, 33, 99]
 /// ```
 /// The first instruction, `[1002, 4, 3, 4]`, is a multiply instruction:   
@@ -62,115 +122,115 @@ impl ParameterMode {
 ///
 /// As such, the program will multiply the value at location `4`(33) with 3
 /// and save the product (99) at location `4`:
-/// ```
+/// ```text
 /// [1002, 4, 3, 4, 99, 99]
 /// ```
 #[derive(Clone, Copy, Debug)]
 enum Opcode {
     /// Adds the numbers in parameters (`src1`, `src2`) and saves the sum in the location specified by (`dst`).
+    /// ```text
+    /// [1(Add), src1(0|1|2), src2(0|1|2), dst(0|2)]
     /// ```
-    /// [1(Add), src1(0|1), src2(0|1), dst(0)]
-    /// ````
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [1001, 5, 2, 0, 99, 40]
     /// ```
     /// This program adds the the number at location `5` with 2,   
     /// saves the sum (42) in memory location `0` and then terminates.  
     /// At the end of the program, the memory will look like the following:
-    /// ```
+    /// ```text
     /// [42, 5, 2, 0, 99, 40]
     /// ```
-    Add(ParameterMode, ParameterMode),
+    Add(ParameterMode, ParameterMode, ParameterMode),
     /// Multiplies the numbers in parameters (`src1`, `src2`) and saves the product in the location specified by (`dst`):  
+    /// ```text
+    /// [2(Multiply), src1(0|1|2), src2(0|1|2), dst(0|2)]
     /// ```
-    /// [2(Multiply), src1(0|1), src2(0|1), dst(0)]
-    /// ````
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [102, 40, 5, 0, 99, 2]
     /// ```
     /// This program multiplies the number 40 with the number at memory location `5` (2),  
     /// saves the product (80) in memory location `0` and then terminates.  
     /// At the end of the program, the memory will look like the following:
-    /// ```
+    /// ```text
     /// [80, 40, 5, 0, 99, 2]
     /// ```
-    Multiply(ParameterMode, ParameterMode),
+    Multiply(ParameterMode, ParameterMode, ParameterMode),
     /// Takes a single integer as input and saves it to memory location `dst`.
-    /// ```
-    /// [3(Input), dst(0)]
+    /// ```text
+    /// [3(Input), dst(0|2)]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [3, 0, 99]
     /// ```
     /// This program will take a single input and save in location `0` .   
     /// Given the input `42`, the memory will look like the following at the program's end:
-    /// ```
+    /// ```text
     /// [42, 0, 99]
     /// ```
-    Input,
+    Input(ParameterMode),
     /// Outputs a single integer value in parameter (`src`).
-    /// ```
-    /// [4(Output), src(0|1)]
+    /// ```text
+    /// [4(Output), src(0|1|2)]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [4, 0, 99]
     /// ```
     /// This program will output a single value in location `0` (4).
     Output(ParameterMode),
     /// Sets the program counter to the second parameter (`loc`) if the first parameter is **non-zero** (`cond`).
-    /// ```
+    /// ```text
     /// [5(JumpIfTrue), cond, loc]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [5, 1, 0, 99]
     /// ```
     /// This program will loop indefinitely.
     JumpIfTrue(ParameterMode, ParameterMode),
     /// Sets the program counter to the second parameter (`loc`) if the first parameter is **zero** (`cond`).
-    /// ```
+    /// ```text
     /// [6(JumpIfFalse), cond, loc]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [6, 0, 0, 99]
     /// ```
     /// This program will loop indefinitely.
     JumpIfFalse(ParameterMode, ParameterMode),
     /// If the first parameter (`operand1`) is less than the second parameter (`operand2`),  
     /// it stores 1 in the position given by the third parameter (`dst`). Otherwise, it stores 0.
-    /// ```
+    /// ```text
     /// [7(LessThan), operand1, operand2, dst]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [1107, 3, 4, 0, 99]
     /// ```
     /// This program will check if `3` is less than `4`, and if so store 1 in position `0` .   
     /// At the end of the program, the memory will look like:
-    /// ```
+    /// ```text
     /// [1, 3, 4, 0, 99]
     /// ```
-    LessThan(ParameterMode, ParameterMode),
+    LessThan(ParameterMode, ParameterMode, ParameterMode),
     /// If the first parameter (`operand1`) is equal to the second parameter (`operand2`),  
     /// it stores 1 in the position given by the third parameter (`dst`). Otherwise, it stores 0.
-    /// ```
+    /// ```text
     /// [8(Equals), operand1, operand2, dst]
     /// ```
     ///
     /// # Example
-    /// ```
+    /// ```text
     /// [1108, 42, 42, 0, 1108, 42, 41, 1, 99]
     /// ```
     /// This program will make two comparisons:
@@ -178,14 +238,26 @@ enum Opcode {
     /// 2. `42` and `41`
     ///
     /// and stores the results in location `0` and `1` respectively:
-    /// ```
+    /// ```text
     /// [1, 0, 42, 0, 1108, 42, 41, 1, 99]
     /// ```
-    Equals(ParameterMode, ParameterMode),
-    /// Terminates the program.
+    Equals(ParameterMode, ParameterMode, ParameterMode),
+    /// Adjusts the relative base by the value of its only parameter (`offset`).
+    /// ```text
+    /// [9(AdjustRelativeBase), offset(0|1|2)]
+    /// ```
     ///
     /// # Example
+    /// ```text
+    /// [109, 19, 204, -15, 99]
     /// ```
+    /// This program will increase the relative base (initially 0) by 19,   
+    /// and then output the value at location `4` (19 - 15), which is 99.
+    AdjustRelativeBase(ParameterMode),
+    /// Terminates the program.
+    ///
+    /// # Example
+    /// ```text
     /// [99, 1, 0, 1, 0]
     /// ```
     /// This program does nothing, as it terminates after executing the   
@@ -193,63 +265,91 @@ enum Opcode {
     Terminate,
 }
 
+/// An error which can occour while decoding an instruction.
+#[derive(Clone, Copy, Debug)]
+enum DecodeError {
+    UnknownOpcode,
+    InvalidParameterMode(i64),
+}
+
 impl Opcode {
-    fn from_i32(i: i32) -> Result<Self, ()> {
+    fn from_i64(i: i64) -> Result<Self, DecodeError> {
+        if i < 0 {
+            return Err(DecodeError::UnknownOpcode);
+        }
         let opcode = i % 100; // get the right two digits
-        let modes = (i / 100) // discard the right two digits
-            .to_string()
-            .chars() // split into digits
-            .rev() // reverse as the parameter modes are specified from right to left
-            .map(|c| ParameterMode::from_i32(c.to_digit(10).unwrap() as i32).unwrap()) // parse each digit into a parameter mode
-            .collect::<Vec<ParameterMode>>();
+        let mut modes = [ParameterMode::Position; 3];
+        let mut digits = i / 100; // discard the right two digits
+        let mut idx = 0;
+        while digits > 0 {
+            // the parameter modes are specified from right to left, one digit each
+            let mode = ParameterMode::from_i64(digits % 10)?;
+            if idx < modes.len() {
+                modes[idx] = mode;
+            }
+            digits /= 10;
+            idx += 1;
+        }
+        let mode = |idx: usize| modes[idx];
         match opcode {
-            1 => Ok(Opcode::Add(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
-            2 => Ok(Opcode::Multiply(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
-            3 => Ok(Opcode::Input),
-            4 => Ok(Opcode::Output(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-            )),
-            5 => Ok(Opcode::JumpIfTrue(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
-            6 => Ok(Opcode::JumpIfFalse(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
-            7 => Ok(Opcode::LessThan(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
-            8 => Ok(Opcode::Equals(
-                *modes.get(0).unwrap_or(&ParameterMode::Position),
-                *modes.get(1).unwrap_or(&ParameterMode::Position),
-            )),
+            1 => Ok(Opcode::Add(mode(0), mode(1), mode(2))),
+            2 => Ok(Opcode::Multiply(mode(0), mode(1), mode(2))),
+            3 => Ok(Opcode::Input(mode(0))),
+            4 => Ok(Opcode::Output(mode(0))),
+            5 => Ok(Opcode::JumpIfTrue(mode(0), mode(1))),
+            6 => Ok(Opcode::JumpIfFalse(mode(0), mode(1))),
+            7 => Ok(Opcode::LessThan(mode(0), mode(1), mode(2))),
+            8 => Ok(Opcode::Equals(mode(0), mode(1), mode(2))),
+            9 => Ok(Opcode::AdjustRelativeBase(mode(0))),
             99 => Ok(Opcode::Terminate),
-            _ => Err(()),
+            _ => Err(DecodeError::UnknownOpcode),
         }
     }
 
     fn instruction_size(self) -> usize {
         use Opcode::*;
         match self {
-            Add(_, _) | Multiply(_, _) | LessThan(_, _) | Equals(_, _) => 4,
-            Input | Output(_) => 2,
+            Add(_, _, _) | Multiply(_, _, _) | LessThan(_, _, _) | Equals(_, _, _) => 4,
+            Input(_) | Output(_) | AdjustRelativeBase(_) => 2,
             Terminate => 1,
             JumpIfFalse(_, _) | JumpIfTrue(_, _) => 3,
         }
     }
+
+    fn parameter_modes(self) -> Vec<ParameterMode> {
+        use Opcode::*;
+        match self {
+            Add(m1, m2, m3) | Multiply(m1, m2, m3) | LessThan(m1, m2, m3) | Equals(m1, m2, m3) => {
+                vec![m1, m2, m3]
+            }
+            JumpIfTrue(m1, m2) | JumpIfFalse(m1, m2) => vec![m1, m2],
+            Input(m1) | Output(m1) | AdjustRelativeBase(m1) => vec![m1],
+            Terminate => vec![],
+        }
+    }
+
+    fn mnemonic(self) -> &'static str {
+        use Opcode::*;
+        match self {
+            Add(_, _, _) => "add",
+            Multiply(_, _, _) => "mul",
+            Input(_) => "in",
+            Output(_) => "out",
+            JumpIfTrue(_, _) => "jt",
+            JumpIfFalse(_, _) => "jf",
+            LessThan(_, _, _) => "lt",
+            Equals(_, _, _) => "eq",
+            AdjustRelativeBase(_) => "arb",
+            Terminate => "hlt",
+        }
+    }
 }
 
 /// Represents an Intcode computer.
 ///
-/// Each instance will *own* its own memory (`Vec<i32>`).
+/// Each instance will *own* its own [`Memory`] of words, where `W` is the [`Word`]  
+/// type of the computer (`i64` by default).  
+/// Enabling the `bigint` feature allows using `num_bigint::BigInt` as an arbitrary-precision word.
 ///
 /// # Example
 /// ```
@@ -259,160 +359,802 @@ impl Opcode {
 ///     let memory = vec![1101, 40, 2, 0, 99];
 ///     let computer = IntcodeComputer::with_stdio(memory);
 ///
-///     println!("The run finished with return value: {}", computer.run_once());
+///     println!("The run finished with return value: {}", computer.run_once().unwrap().result);
 /// }
 /// ```
 ///
 /// This will create a new computer with a simple program that increments two numbers.  
-/// At the end of the run (`run`/`run_once`) the value at location (0) of the memory will  
-/// be returned.
+/// At the end of the run (`run`/`run_once`) a [`RunReport`] is returned, holding the value at  
+/// location (0) of the memory, the final memory, the outputs and the number of executed instructions.  
+/// If the program is faulty (e.g. it contains an unknown opcode), an [`IntcodeError`] is returned instead.
+///
+/// An [`Observer`] can be attached with `with_observer` to trace the execution,  
+/// and watchpoints can be set with `watch` to pause it when the memory is accessed.
 ///
 /// *NOTE*: run_once consumes the memory, and as such can only be called once
-pub struct IntcodeComputer<I: Input, O: Output> {
-    memory: Vec<i32>,
+///
+/// [`Observer`]: trait.Observer.html
+/// [`RunReport`]: struct.RunReport.html
+pub struct IntcodeComputer<I: Input<W>, O: Output<W>, W: Word = i64, B: Observer<W> = ()> {
+    memory: Memory<W>,
+    pc: usize,
+    relative_base: i64,
+    pending_input: VecDeque<W>,
     input: I,
     output: O,
+    observer: B,
+    watches: Vec<Watch<W>>,
+    triggered: Option<Watchpoint<W>>,
+    hits: Vec<Watchpoint<W>>,
+    limits: Limits,
+    deadline: Option<Instant>,
+    executed: u64,
+    outputs: Vec<W>,
 }
 
-impl IntcodeComputer<Stdin, Stdout> {
+impl<W: Word> IntcodeComputer<Stdin, Stdout, W> {
     /// Creates a new instance of `IntcodeComputer` that uses  
     /// `Stdin` and `Stdout` as the input and output streams.
-    pub fn with_stdio(memory: Vec<i32>) -> Self {
+    pub fn with_stdio(memory: Vec<W>) -> Self {
         Self::new(memory, io::stdin(), io::stdout())
     }
 }
 
-impl<I: Input, O: Output> IntcodeComputer<I, O> {
-    pub fn new(memory: Vec<i32>, input: I, output: O) -> Self {
+impl<W: Word, I: Input<W>, O: Output<W>> IntcodeComputer<I, O, W> {
+    pub fn new(memory: Vec<W>, input: I, output: O) -> Self {
         Self {
-            memory,
+            memory: memory.into(),
+            pc: 0,
+            relative_base: 0,
+            pending_input: VecDeque::new(),
             input,
             output,
+            observer: (),
+            watches: Vec::new(),
+            triggered: None,
+            hits: Vec::new(),
+            limits: Limits::default(),
+            deadline: None,
+            executed: 0,
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Creates a new instance of `IntcodeComputer` that resumes the execution from the given snapshot.
+    pub fn from_snapshot(snapshot: Snapshot<W>, input: I, output: O) -> Self {
+        let mut computer = Self::new(Vec::new(), input, output);
+        computer.limits.memory = snapshot.memory.limit();
+        computer.memory = snapshot.memory;
+        computer.pc = snapshot.pc;
+        computer.relative_base = snapshot.relative_base;
+        computer.pending_input = snapshot.pending_input;
+        computer
+    }
+}
+
+impl<W: Word, I: Input<W>, O: Output<W>, B: Observer<W>> IntcodeComputer<I, O, W, B> {
+    /// Attaches the given [`Observer`] to the computer, replacing the current one.
+    ///
+    /// [`Observer`]: trait.Observer.html
+    pub fn with_observer<B2: Observer<W>>(self, observer: B2) -> IntcodeComputer<I, O, W, B2> {
+        IntcodeComputer {
+            memory: self.memory,
+            pc: self.pc,
+            relative_base: self.relative_base,
+            pending_input: self.pending_input,
+            input: self.input,
+            output: self.output,
+            observer,
+            watches: self.watches,
+            triggered: self.triggered,
+            hits: self.hits,
+            limits: self.limits,
+            deadline: self.deadline,
+            executed: self.executed,
+            outputs: self.outputs,
         }
     }
 
-    fn run_impl(&mut self) -> i32 {
-        // helper function to get the correct value of a parameter
-        fn get_value(memory: &[i32], idx: usize, mode: ParameterMode) -> i32 {
-            match mode {
-                ParameterMode::Position => memory[memory[idx] as usize],
-                ParameterMode::Immediate => memory[idx],
+    /// Returns the observer attached to the computer.
+    pub fn observer(&self) -> &B {
+        &self.observer
+    }
+
+    /// Returns the observer attached to the computer, allowing it to be changed.
+    pub fn observer_mut(&mut self) -> &mut B {
+        &mut self.observer
+    }
+
+    /// Consumes the computer, returning its observer.
+    pub fn into_observer(self) -> B {
+        self.observer
+    }
+
+    /// Returns the output stream of the computer.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Returns the output stream of the computer, allowing e.g. collected values to be taken.
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    /// Sets the limit of the computer's memory.
+    ///
+    /// The memory grows as the program accesses locations beyond its end,  
+    /// a limit can be used to stop runaway programs from growing it indefinitely.  
+    /// `None` (the default) means the memory is unlimited.
+    pub fn set_memory_limit(&mut self, limit: Option<usize>) {
+        self.limits.memory = limit;
+        self.memory.set_limit(limit);
+    }
+
+    /// Sets the resource limits of the computer.
+    ///
+    /// When a limit is exceeded the computer halts with `HaltReason::LimitExceeded`, before executing  
+    /// the offending instruction, so its state can be inspected and the execution resumed after raising the limit.  
+    /// The instruction limit counts every instruction executed so far (see `instructions_executed`),  
+    /// while the time limit is counted from the start of each run (`run`, `run_compiled`, ...),  
+    /// or from the first step after the limits are set or a snapshot is restored.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::io;
+    ///
+    /// let memory = vec![1105, 1, 0, 99];
+    /// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+    /// computer.set_limits(Limits {
+    ///     instructions: Some(1000),
+    ///     ..Limits::default()
+    /// });
+    ///
+    /// assert!(matches!(
+    ///     computer.run_until_io(),
+    ///     HaltReason::LimitExceeded(LimitExceeded::Instructions { limit: 1000 })
+    /// ));
+    /// assert_eq!(computer.instructions_executed(), 1000);
+    /// ```
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.memory.set_limit(limits.memory);
+        self.deadline = None;
+        self.limits = limits;
+    }
+
+    /// Returns the resource limits of the computer.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Returns the number of instructions the computer executed.
+    pub fn instructions_executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// Returns the memory of the computer.
+    pub fn memory(&self) -> &Memory<W> {
+        &self.memory
+    }
+
+    /// Returns the memory of the computer, allowing it to be patched.
+    pub fn memory_mut(&mut self) -> &mut Memory<W> {
+        &mut self.memory
+    }
+
+    /// Returns the value at the given location of the memory.
+    ///
+    /// Fails if the location is at or above the memory limit.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::io;
+    ///
+    /// let mut computer = IntcodeComputer::new(vec![1, 2, 3], io::stdin(), io::stdout());
+    /// computer.set_memory_limit(Some(10));
+    ///
+    /// computer.poke(5, 42).unwrap();
+    /// assert_eq!(computer.peek(5).unwrap(), 42);
+    /// assert_eq!(computer.peek(1).unwrap(), 2);
+    /// assert!(computer.peek(10).is_err());
+    /// assert!(computer.poke(10, 1).is_err());
+    /// ```
+    pub fn peek(&self, address: usize) -> Result<W, MemoryLimitExceeded> {
+        self.memory.get(address).cloned()
+    }
+
+    /// Writes the value to the given location of the memory.
+    ///
+    /// Fails if the location is at or above the memory limit.
+    pub fn poke(&mut self, address: usize, value: W) -> Result<(), MemoryLimitExceeded> {
+        self.memory.set(address, value)
+    }
+
+    /// Applies the writes of the given [`Patch`] to the memory, in order.
+    ///
+    /// Fails on the first write to a location at or above the memory limit,
+    /// in which case the writes before it are kept.
+    ///
+    /// [`Patch`]: struct.Patch.html
+    pub fn apply_patch(&mut self, patch: &Patch<W>) -> Result<(), MemoryLimitExceeded> {
+        for (address, value) in patch.writes() {
+            self.poke(address, value.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current *program counter* of the computer.
+    ///
+    /// The program counter is the memory location of the next instruction to execute.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the current *relative base* of the computer.
+    ///
+    /// The relative base starts at 0, and is adjusted  
+    /// by the `AdjustRelativeBase` (`9`) instruction.
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// Returns the values that were queued using `push_input` and were not consumed yet.
+    pub fn pending_input(&self) -> &VecDeque<W> {
+        &self.pending_input
+    }
+
+    /// Queues a value to be consumed by the next `Input` (`3`) instruction.
+    ///
+    /// Queued values are consumed before the input stream is used.
+    pub fn push_input(&mut self, value: W) {
+        self.pending_input.push_back(value);
+    }
+
+    /// Sets a watchpoint on the given range of memory locations.
+    ///
+    /// When an instruction accesses a watched location in the given way, the instruction completes  
+    /// and the computer halts with a [`Watchpoint`] report (see `HaltReason::Watchpoint`).  
+    /// If the instruction also outputs a value, the output is returned first.  
+    /// The runs that go on until the program terminates (`run`/`run_once`/`run_compiled`/`run_async`)
+    /// don't halt, and collect the reports in `RunReport::watchpoints` instead.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::io;
+    ///
+    /// let memory = vec![1101, 2, 3, 5, 99, 0];
+    /// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+    /// computer.watch(5..6, WatchKind::Write);
+    ///
+    /// let report = computer.run().unwrap();
+    /// assert_eq!(report.watchpoints, vec![Watchpoint { addr: 5, old: 0, new: 5, pc: 0 }]);
+    ///
+    /// match computer.run_until_io() {
+    ///     HaltReason::Watchpoint(watchpoint) => {
+    ///         assert_eq!(watchpoint, Watchpoint { addr: 5, old: 0, new: 5, pc: 0 })
+    ///     }
+    ///     reason => panic!("unexpected halt reason: {:?}", reason),
+    /// }
+    /// assert!(matches!(computer.run_until_io(), HaltReason::Halted));
+    /// ```
+    ///
+    /// [`Watchpoint`]: struct.Watchpoint.html
+    pub fn watch(&mut self, addresses: Range<usize>, kind: WatchKind<W>) {
+        self.watches.push(Watch { addresses, kind });
+    }
+
+    /// Removes all the watchpoints.
+    pub fn clear_watchpoints(&mut self) {
+        self.watches.clear();
+    }
+
+    /// Takes a snapshot of the computer's execution state.
+    ///
+    /// See [`Snapshot`] for an example.
+    ///
+    /// [`Snapshot`]: struct.Snapshot.html
+    pub fn snapshot(&self) -> Snapshot<W> {
+        Snapshot {
+            memory: self.memory.clone(),
+            pc: self.pc,
+            relative_base: self.relative_base,
+            pending_input: self.pending_input.clone(),
+        }
+    }
+
+    /// Restores the execution state from the given snapshot.
+    ///
+    /// The observer, watchpoints, limits and the instruction count of the computer are kept.
+    pub fn restore(&mut self, snapshot: &Snapshot<W>) {
+        self.memory = snapshot.memory.clone();
+        self.memory.set_limit(self.limits.memory);
+        self.pc = snapshot.pc;
+        self.relative_base = snapshot.relative_base;
+        self.pending_input = snapshot.pending_input.clone();
+        self.triggered = None;
+        self.deadline = None;
+    }
+
+    /// Creates a [`SymbolicComputer`] that continues from the computer's execution state.
+    ///
+    /// The memory limit of the computer is kept, while its other limits are replaced
+    /// by the default limits of the symbolic computer.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    ///
+    /// use std::io;
+    ///
+    /// let computer = IntcodeComputer::new(vec![1101, 0, 7, 0, 99], io::stdin(), io::stdout());
+    /// let mut symbolic = computer.to_symbolic();
+    /// let noun = symbolic.symbolize(1, "noun");
+    ///
+    /// let (path, solution) = symbolic
+    ///     .find(|path| Some(vec![Constraint::equal(path.memory(0)?, 12)]))
+    ///     .unwrap();
+    /// assert_eq!(path.memory(0).unwrap().to_string(), "noun + 7");
+    /// assert_eq!(solution.value(&noun), Some(5));
+    /// ```
+    ///
+    /// [`SymbolicComputer`]: struct.SymbolicComputer.html
+    pub fn to_symbolic(&self) -> SymbolicComputer<W> {
+        SymbolicComputer::from_parts(
+            self.memory.clone(),
+            self.pc,
+            self.relative_base,
+            self.pending_input.clone(),
+        )
+    }
+
+    // helper function to read a memory location
+    fn read(&self, address: usize) -> Result<W, Fault<W>> {
+        Ok(self.memory.get(address)?.clone())
+    }
+
+    // helper function to write to a memory location
+    fn write(&mut self, address: usize, value: W) -> Result<(), Fault<W>> {
+        if B::ENABLED || !self.watches.is_empty() {
+            let old = self.read(address)?;
+            if B::ENABLED {
+                self.observer.on_write(address, &old, &value);
+            }
+            let triggered = self
+                .watches
+                .iter()
+                .any(|watch| watch.on_write(address, &value));
+            if triggered && self.triggered.is_none() {
+                self.triggered = Some(Watchpoint {
+                    addr: address,
+                    old,
+                    new: value.clone(),
+                    pc: self.pc,
+                });
             }
         }
+        Ok(self.memory.set(address, value)?)
+    }
 
-        // run the program
-        let mut pc = 0usize;
-        loop {
-            let opcode = Opcode::from_i32(self.memory[pc]).unwrap(); // get the opcode from the first two digits
-            match opcode {
-                Opcode::Add(src1_mode, src2_mode) => {
-                    // get the parameters
-                    let src1 = get_value(&self.memory, pc + 1, src1_mode);
-                    let src2 = get_value(&self.memory, pc + 2, src2_mode);
-                    let dst = self.memory[pc + 3] as usize; // always in position mode
-
-                    // perform the operation
-                    self.memory[dst] = src1 + src2;
+    // helper function to get the memory location a parameter points to
+    fn address(&self, idx: usize, mode: ParameterMode) -> Result<usize, Fault<W>> {
+        let param = self.read(idx)?;
+        let address = match mode {
+            ParameterMode::Position => param,
+            ParameterMode::Relative => W::checked_from_i64(self.relative_base)
+                .and_then(|base| base.checked_add(&param))
+                .ok_or(Fault::Overflow)?,
+            ParameterMode::Immediate => return Err(Fault::ImmediateWrite),
+        };
+        match address.to_address() {
+            Some(address) => Ok(address),
+            None => Err(Fault::InvalidAddress(address)),
+        }
+    }
+
+    // helper function to get the correct value of a parameter
+    fn value(&mut self, idx: usize, mode: ParameterMode) -> Result<W, Fault<W>> {
+        match mode {
+            ParameterMode::Immediate => self.read(idx),
+            _ => {
+                let address = self.address(idx, mode)?;
+                let value = self.read(address)?;
+                if B::ENABLED {
+                    self.observer.on_read(address, &value);
                 }
-                Opcode::Multiply(src1_mode, src2_mode) => {
-                    // get the parameters
-                    let src1 = get_value(&self.memory, pc + 1, src1_mode);
-                    let src2 = get_value(&self.memory, pc + 2, src2_mode);
-                    let dst = self.memory[pc + 3] as usize; // always in position mode
-
-                    // perform the operation
-                    self.memory[dst] = src1 * src2;
+                let triggered = self.watches.iter().any(|watch| watch.on_read(address));
+                if triggered && self.triggered.is_none() {
+                    self.triggered = Some(Watchpoint {
+                        addr: address,
+                        old: value.clone(),
+                        new: value.clone(),
+                        pc: self.pc,
+                    });
                 }
-                Opcode::Input => {
-                    // get the parameters
-                    let input = self.input.get().expect("Input was not i32"); // TODO: handle wrong input gracefully
-                    let dst = self.memory[pc + 1] as usize; // always in position mode
+                Ok(value)
+            }
+        }
+    }
 
-                    // perform the operation
-                    self.memory[dst] = input;
-                }
-                Opcode::Output(src_mode) => {
-                    // get the parameter
-                    let src = get_value(&self.memory, pc + 1, src_mode);
+    /// Executes the given instruction, which is located at the program counter.
+    ///
+    /// Returns the reason the computer halted, if the instruction needs input,  
+    /// outputs a value or terminates the program.
+    fn execute(&mut self, instruction: &W) -> Result<Option<HaltReason<W>>, Fault<W>> {
+        // helper function to turn a comparison into a value
+        fn from_bool<W: Word>(value: bool) -> W {
+            W::from_i64(if value { 1 } else { 0 })
+        }
 
-                    // perform the operation
-                    self.output.push(src);
+        let pc = self.pc;
+
+        // get the opcode from the first two digits
+        let opcode = instruction
+            .to_i64()
+            .ok_or(DecodeError::UnknownOpcode)
+            .and_then(Opcode::from_i64)?;
+        match opcode {
+            Opcode::Add(src1_mode, src2_mode, dst_mode) => {
+                // get the parameters
+                let src1 = self.value(pc + 1, src1_mode)?;
+                let src2 = self.value(pc + 2, src2_mode)?;
+                let dst = self.address(pc + 3, dst_mode)?;
+
+                // perform the operation
+                self.write(dst, src1.checked_add(&src2).ok_or(Fault::Overflow)?)?;
+            }
+            Opcode::Multiply(src1_mode, src2_mode, dst_mode) => {
+                // get the parameters
+                let src1 = self.value(pc + 1, src1_mode)?;
+                let src2 = self.value(pc + 2, src2_mode)?;
+                let dst = self.address(pc + 3, dst_mode)?;
+
+                // perform the operation
+                self.write(dst, src1.checked_mul(&src2).ok_or(Fault::Overflow)?)?;
+            }
+            Opcode::Input(dst_mode) => {
+                // get the parameters
+                let dst = self.address(pc + 1, dst_mode)?;
+                let input = match self.pending_input.pop_front() {
+                    Some(input) => input,
+                    None => return Ok(Some(HaltReason::NeedsInput)),
+                };
+                if B::ENABLED {
+                    self.observer.on_input(&input);
                 }
-                Opcode::JumpIfTrue(cond_mode, loc_mode) => {
-                    // get the parameters
-                    let cond = get_value(&self.memory, pc + 1, cond_mode);
-                    let loc = get_value(&self.memory, pc + 2, loc_mode) as usize;
-
-                    // perform the operation
-                    if cond != 0 {
-                        pc = loc.wrapping_sub(opcode.instruction_size());
-                    }
+
+                // perform the operation
+                self.write(dst, input)?;
+            }
+            Opcode::Output(src_mode) => {
+                // get the parameter
+                let src = self.value(pc + 1, src_mode)?;
+
+                // perform the operation
+                if B::ENABLED {
+                    self.observer.on_output(&src);
                 }
-                Opcode::JumpIfFalse(cond_mode, loc_mode) => {
-                    // get the parameters
-                    let cond = get_value(&self.memory, pc + 1, cond_mode);
-                    let loc = get_value(&self.memory, pc + 2, loc_mode) as usize;
-
-                    // perform the operation
-                    if cond == 0 {
-                        pc = loc.wrapping_sub(opcode.instruction_size());
-                    }
+                self.pc = pc + opcode.instruction_size();
+                return Ok(Some(HaltReason::Output(src)));
+            }
+            Opcode::JumpIfTrue(cond_mode, loc_mode) | Opcode::JumpIfFalse(cond_mode, loc_mode) => {
+                // get the parameters
+                let cond = self.value(pc + 1, cond_mode)?;
+                let loc = self.value(pc + 2, loc_mode)?;
+
+                // perform the operation
+                let jump = match opcode {
+                    Opcode::JumpIfTrue(_, _) => !cond.is_zero(),
+                    _ => cond.is_zero(),
+                };
+                if jump {
+                    self.pc = match loc.to_address() {
+                        Some(loc) => loc,
+                        None => return Err(Fault::InvalidAddress(loc)),
+                    };
+                    return Ok(None);
                 }
-                Opcode::LessThan(operand1_mode, operand2_mode) => {
-                    // get the parameters
-                    let operand1 = get_value(&self.memory, pc + 1, operand1_mode);
-                    let operand2 = get_value(&self.memory, pc + 2, operand2_mode);
-                    let dst = self.memory[pc + 3] as usize; // always in position mode
-
-                    // perform the operation
-                    self.memory[dst] = if operand1 < operand2 { 1 } else { 0 }
+            }
+            Opcode::LessThan(operand1_mode, operand2_mode, dst_mode) => {
+                // get the parameters
+                let operand1 = self.value(pc + 1, operand1_mode)?;
+                let operand2 = self.value(pc + 2, operand2_mode)?;
+                let dst = self.address(pc + 3, dst_mode)?;
+
+                // perform the operation
+                self.write(dst, from_bool(operand1 < operand2))?;
+            }
+            Opcode::Equals(operand1_mode, operand2_mode, dst_mode) => {
+                // get the parameters
+                let operand1 = self.value(pc + 1, operand1_mode)?;
+                let operand2 = self.value(pc + 2, operand2_mode)?;
+                let dst = self.address(pc + 3, dst_mode)?;
+
+                // perform the operation
+                self.write(dst, from_bool(operand1 == operand2))?;
+            }
+            Opcode::AdjustRelativeBase(offset_mode) => {
+                // get the parameter
+                let offset = self.value(pc + 1, offset_mode)?;
+
+                // perform the operation
+                self.relative_base = offset
+                    .to_i64()
+                    .and_then(|offset| self.relative_base.checked_add(offset))
+                    .ok_or(Fault::Overflow)?;
+            }
+            Opcode::Terminate => return Ok(Some(HaltReason::Halted)),
+        };
+        self.pc = pc + opcode.instruction_size();
+        Ok(None)
+    }
+
+    // helper function to turn a fault into an error at the current instruction
+    fn fault_at_pc(&self, fault: Fault<W>) -> IntcodeError<W> {
+        let instruction = self.read(self.pc).unwrap_or_else(|_| W::from_i64(0));
+        fault.at(self.pc, instruction)
+    }
+
+    /// Executes a single instruction.
+    ///
+    /// Returns `None` if the execution can simply continue, or the reason the computer halted.  
+    /// When halting because of `NeedsInput`, `Halted` or `Error` the program counter does not move,  
+    /// so stepping again (e.g. after pushing an input) will retry the same instruction.  
+    /// A `Watchpoint` that was triggered by an instruction which also outputted a value  
+    /// is returned by the next step, without executing another instruction.
+    pub fn step(&mut self) -> Option<HaltReason<W>> {
+        if let Some(watchpoint) = self.triggered.take() {
+            return Some(HaltReason::Watchpoint(watchpoint));
+        }
+        if let Some(limit) = self.check_limits() {
+            return Some(HaltReason::LimitExceeded(limit));
+        }
+        let instruction = match self.read(self.pc) {
+            Ok(instruction) => instruction,
+            Err(Fault::LimitExceeded(limit)) => return Some(HaltReason::LimitExceeded(limit)),
+            Err(fault) => return Some(HaltReason::Error(self.fault_at_pc(fault))),
+        };
+        if B::ENABLED {
+            self.observer.on_fetch(self.pc, &instruction);
+        }
+        let reason = match self.execute(&instruction) {
+            Ok(None) => self.triggered.take().map(HaltReason::Watchpoint),
+            Ok(reason) => reason,
+            Err(fault) => {
+                self.triggered = None;
+                return Some(match fault {
+                    Fault::LimitExceeded(limit) => HaltReason::LimitExceeded(limit),
+                    fault => HaltReason::Error(fault.at(self.pc, instruction)),
+                });
+            }
+        };
+        // instructions that halt without completing don't count
+        match reason {
+            Some(HaltReason::NeedsInput) | Some(HaltReason::Halted) => {}
+            _ => self.executed += 1,
+        }
+        reason
+    }
+
+    // helper function to check the instruction and time limits before executing an instruction
+    fn check_limits(&mut self) -> Option<LimitExceeded> {
+        if let Some(limit) = self.limits.instructions {
+            if self.executed >= limit {
+                return Some(LimitExceeded::Instructions { limit });
+            }
+        }
+        if let Some(limit) = self.limits.time {
+            // the clock starts with the first instruction of a run
+            let deadline = *self.deadline.get_or_insert_with(|| Instant::now() + limit);
+            if self.executed.is_multiple_of(TIME_CHECK_INTERVAL) && Instant::now() >= deadline {
+                return Some(LimitExceeded::Time { limit });
+            }
+        }
+        None
+    }
+
+    /// Runs the program until it needs input, outputs a value, triggers a watchpoint,  
+    /// exceeds a limit, terminates or fails.
+    ///
+    /// The execution can later be resumed from the same spot by calling `run_until_io` (or `step`) again.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::io;
+    ///
+    /// let memory = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+    /// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+    ///
+    /// assert!(matches!(computer.run_until_io(), HaltReason::NeedsInput));
+    /// computer.push_input(41);
+    /// assert!(matches!(computer.run_until_io(), HaltReason::Output(42)));
+    /// assert!(matches!(computer.run_until_io(), HaltReason::Halted));
+    /// ```
+    pub fn run_until_io(&mut self) -> HaltReason<W> {
+        loop {
+            if let Some(reason) = self.step() {
+                return reason;
+            }
+        }
+    }
+
+    fn run_impl(&mut self) -> Result<W, IntcodeError<W>> {
+        // run the program, using the streams for input and output
+        loop {
+            match self.run_until_io() {
+                HaltReason::NeedsInput => match self.input.get() {
+                    Ok(input) => self.push_input(input),
+                    Err(err) => return Err(self.fault_at_pc(Fault::Input(err))),
+                },
+                HaltReason::Output(value) => {
+                    self.outputs.push(value.clone());
+                    self.output.push(value)
                 }
-                Opcode::Equals(operand1_mode, operand2_mode) => {
-                    // get the parameters
-                    let operand1 = get_value(&self.memory, pc + 1, operand1_mode);
-                    let operand2 = get_value(&self.memory, pc + 2, operand2_mode);
-                    let dst = self.memory[pc + 3] as usize; // always in position mode
-
-                    // perform the operation
-                    self.memory[dst] = if operand1 == operand2 { 1 } else { 0 }
+                HaltReason::Watchpoint(watchpoint) => self.hits.push(watchpoint),
+                HaltReason::LimitExceeded(limit) => {
+                    return Err(self.fault_at_pc(Fault::LimitExceeded(limit)))
                 }
-                Opcode::Terminate => break,
-            };
-            pc = pc.wrapping_add(opcode.instruction_size());
+                HaltReason::Halted => return self.read(0).map_err(|fault| self.fault_at_pc(fault)),
+                HaltReason::Error(err) => return Err(err),
+            }
         }
-        self.memory[0]
+    }
+
+    // helper function to build the report of a run that started after `started` instructions
+    pub(crate) fn report(
+        &mut self,
+        result: Result<W, IntcodeError<W>>,
+        started: u64,
+    ) -> Result<RunReport<W>, IntcodeError<W>> {
+        let outputs = mem::take(&mut self.outputs);
+        let watchpoints = mem::take(&mut self.hits);
+        Ok(RunReport {
+            result: result?,
+            memory: self.memory.clone(),
+            outputs,
+            watchpoints,
+            steps: self.executed - started,
+        })
     }
 
     /// Runs program and consumes the memory.
     ///
+    /// The program runs from the current program counter until it terminates.  
     /// This methods drops `self`, but it prevents needless copies
     /// of the memory.
-    pub fn run_once(mut self) -> i32 {
-        self.run_impl()
+    pub fn run_once(mut self) -> Result<RunReport<W>, IntcodeError<W>> {
+        self.deadline = None;
+        let started = self.executed;
+        let result = self.run_impl();
+        self.report(result, started)
     }
 
     /// Runs program and without consuming the memory.
     ///
-    /// Can be run multiple times, but requires copying the memory
-    /// each time.
-    pub fn run(&mut self) -> i32 {
-        // clone the memory to restore later
-        let memory = self.memory.clone();
-        let output = self.run_impl();
+    /// Can be run multiple times: the memory is restored after each run.  
+    /// Since the memory is copy-on-write, only the pages the program writes to are copied.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::{io, sync::mpsc};
+    ///
+    /// let (tx, _rx) = mpsc::channel();
+    /// let memory = vec![1101, 40, 2, 0, 4, 0, 99];
+    /// let mut computer = IntcodeComputer::new(memory, io::stdin(), tx);
+    /// let report = computer.run().unwrap();
+    ///
+    /// assert_eq!(report.result, 42);
+    /// assert_eq!(report.outputs, vec![42]);
+    /// assert_eq!(report.steps, 2);
+    /// assert_eq!(*report.memory.get(0).unwrap(), 42);
+    /// assert_eq!(computer.peek(0).unwrap(), 1101);
+    /// ```
+    pub fn run(&mut self) -> Result<RunReport<W>, IntcodeError<W>> {
+        // take a snapshot of the state to restore later
+        let snapshot = self.snapshot();
+        // the time limit counts from the start of the run
+        self.deadline = None;
+        let started = self.executed;
+        let result = self.run_impl();
+        let report = self.report(result, started);
+        self.restore(&snapshot);
+
+        report
+    }
 
-        // restore the memory
-        self.memory = memory;
+    /// Runs the program with the given inputs, without consuming the memory, and returns its outputs.
+    ///
+    /// The inputs are read before any value from the input stream, and like `run`  
+    /// the state is restored after the run. The outputs are pushed to the output stream as well.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::collections::VecDeque;
+    ///
+    /// // outputs the sum of two inputs
+    /// let memory = vec![3, 11, 3, 12, 1, 11, 12, 11, 4, 11, 99, 0, 0];
+    /// let mut computer = IntcodeComputer::new(memory, VecDeque::new(), |_| {});
+    ///
+    /// assert_eq!(computer.run_with_inputs(&[40, 2]).unwrap(), vec![42]);
+    /// assert_eq!(computer.run_with_inputs(&[1, 2]).unwrap(), vec![3]);
+    /// assert!(computer.run_with_inputs(&[1]).is_err());
+    /// ```
+    pub fn run_with_inputs(&mut self, inputs: &[W]) -> Result<Vec<W>, IntcodeError<W>> {
+        // take a snapshot of the state to restore later
+        let snapshot = self.snapshot();
+        self.deadline = None;
+        let started = self.executed;
+        self.pending_input.extend(inputs.iter().cloned());
+        let result = self.run_impl();
+        let report = self.report(result, started);
+        self.restore(&snapshot);
 
-        output
+        report.map(|report| report.outputs)
     }
 
-    /// Sets the noun and the verb of the program.
-    pub fn set(&mut self, noun: i32, verb: i32) {
-        self.memory[1] = noun;
-        self.memory[2] = verb;
+    /// Sets the noun and the verb of the program (locations `1` and `2`).
+    ///
+    /// Fails if the memory limit is too small to hold them, in which case the memory is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    /// use std::io;
+    ///
+    /// let mut computer = IntcodeComputer::new(vec![1, 0, 0, 0, 99], io::stdin(), io::stdout());
+    /// computer.set(4, 4).unwrap();
+    /// assert_eq!(computer.run().unwrap().result, 198);
+    ///
+    /// computer.set_memory_limit(Some(2));
+    /// assert!(computer.set(0, 0).is_err());
+    /// assert_eq!(computer.peek(1).unwrap(), 4);
+    /// ```
+    pub fn set(&mut self, noun: W, verb: W) -> Result<(), MemoryLimitExceeded> {
+        // check the limit first, so that the noun isn't set without the verb
+        self.peek(2)?;
+        self.poke(1, noun)?;
+        self.poke(2, verb)
     }
 }
 
+/// The outcome of a run of an [`IntcodeComputer`] that terminated (`run`/`run_once`/`run_compiled`).
+#[derive(Clone, Debug)]
+pub struct RunReport<W: Word> {
+    /// The value at location (0) of the memory when the program terminated.
+    pub result: W,
+    /// The memory when the program terminated.
+    pub memory: Memory<W>,
+    /// The values the program outputted during the run.
+    pub outputs: Vec<W>,
+    /// The watchpoints that were triggered during the run, in order.
+    pub watchpoints: Vec<Watchpoint<W>>,
+    /// The number of instructions executed during the run.
+    pub steps: u64,
+}
+
+/// The reason an [`IntcodeComputer`] stopped executing the program.
+///
+/// Returned by the resumable API (`step`/`run_until_io`).
+#[derive(Debug)]
+pub enum HaltReason<W: Word> {
+    /// An `Input` (`3`) instruction was reached, but no input was queued (see `push_input`).
+    NeedsInput,
+    /// The program outputted the given value.
+    Output(W),
+    /// A watchpoint set with `watch` was triggered.
+    Watchpoint(Watchpoint<W>),
+    /// One of the limits set with `set_limits` (or `set_memory_limit`) was exceeded.
+    LimitExceeded(LimitExceeded),
+    /// The program terminated.
+    Halted,
+    /// The program is faulty.
+    Error(IntcodeError<W>),
+}
+
 /// An error which can occour while using the input/output streams.
 #[derive(Debug)]
 pub struct StreamError {
@@ -425,12 +1167,20 @@ impl Display for StreamError {
     }
 }
 
-impl error::Error for StreamError {}
+impl std::error::Error for StreamError {}
 
 impl From<ParseIntError> for StreamError {
     fn from(_: ParseIntError) -> Self {
         Self {
-            msg: String::from("Could not parse the given input into an i32"),
+            msg: String::from("Could not parse the given input into a word"),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self {
+            msg: format!("Could not read from the input stream: {}", err),
         }
     }
 }
@@ -444,41 +1194,101 @@ impl From<mpsc::RecvError> for StreamError {
 }
 
 /// Represents an input stream that can be used by the [`IntcodeComputer`].
-pub trait Input {
-    /// Gets the next `i32` value from the input stream.
-    fn get(&mut self) -> Result<i32, StreamError>;
+pub trait Input<W: Word> {
+    /// Gets the next value from the input stream.
+    fn get(&mut self) -> Result<W, StreamError>;
 }
 
 /// Represents an output stream that can be used by the [`IntcodeComputer`].
-pub trait Output {
-    /// Pushes the given `i32` value into the output stream.
-    fn push(&mut self, value: i32);
+pub trait Output<W: Word> {
+    /// Pushes the given value into the output stream.
+    fn push(&mut self, value: W);
 }
 
-impl Input for Stdin {
-    fn get(&mut self) -> Result<i32, StreamError> {
+impl<W: Word> Input<W> for Stdin {
+    fn get(&mut self) -> Result<W, StreamError> {
         let mut buffer = String::new();
-        self.read_line(&mut buffer).unwrap();
-        let value = buffer.trim().parse::<i32>()?;
+        if self.read_line(&mut buffer)? == 0 {
+            return Err(StreamError {
+                msg: String::from("The input stream was closed"),
+            });
+        }
+        let value = buffer.trim().parse::<W>().map_err(|_| StreamError {
+            msg: String::from("Could not parse the given input into a word"),
+        })?;
         Ok(value)
     }
 }
 
-impl Output for Stdout {
-    fn push(&mut self, value: i32) {
+impl<W: Word> Output<W> for Stdout {
+    fn push(&mut self, value: W) {
         self.write_all(format!("{}\n", value).as_bytes()).unwrap();
     }
 }
 
-impl Input for Receiver<i32> {
-    fn get(&mut self) -> Result<i32, StreamError> {
+impl<W: Word> Input<W> for Receiver<W> {
+    fn get(&mut self) -> Result<W, StreamError> {
         let value = self.recv()?;
         Ok(value)
     }
 }
 
-impl Output for Sender<i32> {
-    fn push(&mut self, value: i32) {
+impl<W: Word> Output<W> for Sender<W> {
+    fn push(&mut self, value: W) {
         self.send(value).unwrap();
     }
 }
+
+// helper function to create the error of an input stream that ran out of values
+fn exhausted() -> StreamError {
+    StreamError {
+        msg: String::from("The input stream ran out of values"),
+    }
+}
+
+impl<W: Word> Input<W> for VecDeque<W> {
+    fn get(&mut self) -> Result<W, StreamError> {
+        self.pop_front().ok_or_else(exhausted)
+    }
+}
+
+impl<W: Word> Input<W> for &[W] {
+    fn get(&mut self) -> Result<W, StreamError> {
+        let (value, rest) = self.split_first().ok_or_else(exhausted)?;
+        *self = rest;
+        Ok(value.clone())
+    }
+}
+
+/// An [`Input`] that takes its values from an iterator.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let memory = vec![3, 0, 99];
+/// let computer = IntcodeComputer::new(memory, IterInput((1..).map(|x| x * 7)), |_| {});
+/// assert_eq!(computer.run_once().unwrap().result, 7);
+/// ```
+///
+/// [`Input`]: trait.Input.html
+#[derive(Clone, Debug)]
+pub struct IterInput<T>(pub T);
+
+impl<W: Word, T: Iterator<Item = W>> Input<W> for IterInput<T> {
+    fn get(&mut self) -> Result<W, StreamError> {
+        self.0.next().ok_or_else(exhausted)
+    }
+}
+
+impl<W: Word> Output<W> for Vec<W> {
+    fn push(&mut self, value: W) {
+        Vec::push(self, value);
+    }
+}
+
+impl<W: Word, F: FnMut(W)> Output<W> for F {
+    fn push(&mut self, value: W) {
+        self(value);
+    }
+}
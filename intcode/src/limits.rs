@@ -0,0 +1,55 @@
+use crate::MemoryLimitExceeded;
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+/// The resource limits of an [`IntcodeComputer`], used to run untrusted programs.
+///
+/// Each limit is optional, and `Limits::default()` sets no limits at all.
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximal number of instructions the computer executes.
+    pub instructions: Option<u64>,
+    /// The size of the memory: accessing a location at or above it fails.
+    pub memory: Option<usize>,
+    /// The maximal time each run of the computer takes, counted from its first instruction.
+    pub time: Option<Duration>,
+}
+
+/// The limit that stopped an [`IntcodeComputer`].
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The given number of instructions was executed.
+    Instructions { limit: u64 },
+    /// The memory was accessed beyond its limit.
+    Memory(MemoryLimitExceeded),
+    /// The given time has elapsed.
+    Time { limit: Duration },
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LimitExceeded::Instructions { limit } => {
+                write!(f, "The instruction limit ({}) was reached", limit)
+            }
+            LimitExceeded::Memory(err) => write!(f, "{}", err),
+            LimitExceeded::Time { limit } => write!(f, "The time limit ({:?}) has elapsed", limit),
+        }
+    }
+}
+
+impl error::Error for LimitExceeded {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LimitExceeded::Memory(err) => Some(err),
+            _ => None,
+        }
+    }
+}
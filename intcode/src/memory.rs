@@ -0,0 +1,224 @@
+use crate::Word;
+use std::{
+    collections::HashMap,
+    error,
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+};
+
+/// The number of words in a single page of the memory.
+const PAGE_SIZE: usize = 1024;
+
+/// The maximal distance (in pages) past the end of the dense memory for which
+/// a write will grow the dense memory instead of using the sparse memory.
+const DENSE_GROWTH_LIMIT: usize = 4;
+
+/// A page of the memory, shared between clones until one of them writes to it.
+type Page<W> = Arc<Vec<W>>;
+
+/// The memory of an [`IntcodeComputer`].
+///
+/// The memory transparently extends with zeros on out-of-range access.
+/// It is made of fixed-size *pages*: a *dense* prefix of pages, which holds the program
+/// and any locations near it, and a *sparse* map of pages for far away locations, so that
+/// writing to location `1_000_000_000` does not allocate gigabytes of memory.
+///
+/// The pages are copy-on-write: cloning the memory only copies references to its pages,
+/// and a page is duplicated the first time one of the clones writes to it.
+/// This makes resetting (`IntcodeComputer::run`) and forking a machine cheap, regardless of the program's size.
+///
+/// An optional limit can be set to catch runaway programs: any access to a location
+/// at or above the limit fails with a [`MemoryLimitExceeded`] error.
+///
+/// # Example
+/// ```
+/// use intcode::Memory;
+///
+/// let mut memory = Memory::new(vec![1, 2, 3]);
+/// memory.set(1_000_000_000, 42).unwrap();
+///
+/// assert_eq!(*memory.get(1_000_000_000).unwrap(), 42);
+/// assert_eq!(*memory.get(500).unwrap(), 0);
+///
+/// let mut fork = memory.clone();
+/// fork.set(1, 7).unwrap();
+/// assert_eq!(*memory.get(1).unwrap(), 2);
+/// assert_eq!(*fork.get(1).unwrap(), 7);
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+/// [`MemoryLimitExceeded`]: struct.MemoryLimitExceeded.html
+#[derive(Clone, Debug)]
+pub struct Memory<W: Word> {
+    dense: Vec<Page<W>>,
+    sparse: HashMap<usize, Page<W>>,
+    len: usize,
+    limit: Option<usize>,
+    zero: W,
+}
+
+impl<W: Word> Memory<W> {
+    /// Creates a new memory whose dense prefix is initialized with the given words.
+    pub fn new(words: Vec<W>) -> Self {
+        let zero = W::from_i64(0);
+        let len = words.len();
+        let dense = words
+            .chunks(PAGE_SIZE)
+            .map(|chunk| {
+                let mut page = Vec::with_capacity(PAGE_SIZE);
+                page.extend_from_slice(chunk);
+                page.resize(PAGE_SIZE, zero.clone());
+                Arc::new(page)
+            })
+            .collect();
+        Self {
+            dense,
+            sparse: HashMap::new(),
+            len,
+            limit: None,
+            zero,
+        }
+    }
+
+    /// Returns the limit of the memory, if one was set.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Sets the limit of the memory.
+    ///
+    /// Accessing a location at or above the limit will fail.
+    /// `None` means the memory is unlimited.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Returns the size of the memory: one past the highest location that was ever stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing was ever stored in the memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at the given location.
+    ///
+    /// Locations that were never written to hold zero.
+    pub fn get(&self, address: usize) -> Result<&W, MemoryLimitExceeded> {
+        self.check_limit(address)?;
+        let (page, offset) = (address / PAGE_SIZE, address % PAGE_SIZE);
+        Ok(match self.dense.get(page) {
+            Some(page) => &page[offset],
+            None => match self.sparse.get(&page) {
+                Some(page) => &page[offset],
+                None => &self.zero,
+            },
+        })
+    }
+
+    /// Sets the value at the given location, growing the memory if needed.
+    ///
+    /// Only the page holding the location is copied, if it is shared with a clone of the memory.
+    pub fn set(&mut self, address: usize, value: W) -> Result<(), MemoryLimitExceeded> {
+        self.check_limit(address)?;
+        let (page, offset) = (address / PAGE_SIZE, address % PAGE_SIZE);
+        let page = if page < self.dense.len() {
+            &mut self.dense[page]
+        } else if page - self.dense.len() < DENSE_GROWTH_LIMIT {
+            self.grow_dense(page + 1);
+            &mut self.dense[page]
+        } else {
+            let zero = &self.zero;
+            self.sparse
+                .entry(page)
+                .or_insert_with(|| Arc::new(vec![zero.clone(); PAGE_SIZE]))
+        };
+        Arc::make_mut(page)[offset] = value;
+        self.len = self.len.max(address + 1);
+        Ok(())
+    }
+
+    /// Grows the dense memory to the given number of pages, moving any
+    /// sparse pages that now belong in the dense memory.
+    fn grow_dense(&mut self, pages: usize) {
+        for page in self.dense.len()..pages {
+            let page = match self.sparse.remove(&page) {
+                Some(page) => page,
+                None => Arc::new(vec![self.zero.clone(); PAGE_SIZE]),
+            };
+            self.dense.push(page);
+        }
+    }
+
+    /// Splits the memory into its dense prefix and its (non-zero) sparse locations.
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_parts(&self) -> (Vec<W>, Vec<(usize, W)>) {
+        let mut dense = self
+            .dense
+            .iter()
+            .flat_map(|page| page.iter().cloned())
+            .collect::<Vec<W>>();
+        dense.truncate(self.len);
+        let mut sparse = self
+            .sparse
+            .iter()
+            .flat_map(|(page, words)| {
+                words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(move |(offset, value)| (page * PAGE_SIZE + offset, value.clone()))
+            })
+            .collect::<Vec<(usize, W)>>();
+        sparse.sort_by_key(|(address, _)| *address);
+        (dense, sparse)
+    }
+
+    /// Builds a memory from the parts returned by `to_parts`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts(dense: Vec<W>, sparse: Vec<(usize, W)>) -> Self {
+        let mut memory = Self::new(dense);
+        for (address, value) in sparse {
+            memory.set(address, value).unwrap();
+        }
+        memory
+    }
+
+    fn check_limit(&self, address: usize) -> Result<(), MemoryLimitExceeded> {
+        match self.limit {
+            Some(limit) if address >= limit => Err(MemoryLimitExceeded { address, limit }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<W: Word> From<Vec<W>> for Memory<W> {
+    fn from(words: Vec<W>) -> Self {
+        Self::new(words)
+    }
+}
+
+/// An error which occurs when accessing a location at or above the limit of a [`Memory`].
+///
+/// [`Memory`]: struct.Memory.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryLimitExceeded {
+    /// The location that was accessed.
+    pub address: usize,
+    /// The limit of the memory.
+    pub limit: usize,
+}
+
+impl Display for MemoryLimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Memory location {} is beyond the memory limit ({})",
+            self.address, self.limit
+        )
+    }
+}
+
+impl error::Error for MemoryLimitExceeded {}
@@ -0,0 +1,211 @@
+use crate::{Fault, HaltReason, IntcodeComputer, IntcodeError, Word};
+use std::collections::VecDeque;
+
+/// The maximal number of instructions a machine of a [`Network`] executes in a single turn.
+///
+/// [`Network`]: struct.Network.html
+const QUANTUM: usize = 1024;
+
+/// The reason a [`Network`] stopped running.
+///
+/// [`Network`]: struct.Network.html
+#[derive(Debug)]
+pub enum NetworkHalt<W: Word> {
+    /// All of the machines terminated.
+    Halted,
+    /// None of the machines can make progress: the given ones wait for input
+    /// that no other machine will send, and the rest terminated.
+    Deadlock(Vec<usize>),
+    /// The given machine is faulty.
+    Error {
+        /// The machine that failed.
+        machine: usize,
+        /// The error of the machine.
+        error: IntcodeError<W>,
+    },
+}
+
+/// A machine of a [`Network`], with the machines its outputs are routed to.
+struct Machine<W: Word> {
+    computer: IntcodeComputer<VecDeque<W>, Vec<W>, W>,
+    routes: Vec<usize>,
+    outputs: Vec<W>,
+    halted: bool,
+}
+
+/// The outcome of a single turn of a machine.
+enum Turn<W: Word> {
+    Progress,
+    Waiting,
+    Error(IntcodeError<W>),
+}
+
+/// A network of machines that send their outputs to each other, run on a single thread.
+///
+/// The machines are run round-robin with the resumable execution (`step`): each one runs until it
+/// waits for input, terminates, or used up its turn. Every output is delivered to the inputs
+/// of the machines the machine is connected to, and is also kept (see `outputs`).
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// // a machine that adds 1 to its input, and another one that doubles it
+/// let add = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+/// let double = vec![3, 9, 102, 2, 9, 9, 4, 9, 99, 0];
+///
+/// let mut network = Network::new();
+/// let first = network.add_machine(add);
+/// let second = network.add_machine(double);
+/// network.connect(first, second);
+///
+/// assert!(matches!(network.run(), NetworkHalt::Deadlock(waiting) if waiting == vec![0, 1]));
+///
+/// network.push_input(first, 20);
+/// assert!(matches!(network.run(), NetworkHalt::Halted));
+/// assert_eq!(network.outputs(second), &[42]);
+/// ```
+pub struct Network<W: Word = i64> {
+    machines: Vec<Machine<W>>,
+}
+
+impl<W: Word> Network<W> {
+    /// Creates a network without any machines.
+    pub fn new() -> Self {
+        Self {
+            machines: Vec::new(),
+        }
+    }
+
+    /// Adds a machine that runs the given program, returning its index.
+    pub fn add_machine(&mut self, memory: Vec<W>) -> usize {
+        self.machines.push(Machine {
+            computer: IntcodeComputer::new(memory, VecDeque::new(), Vec::new()),
+            routes: Vec::new(),
+            outputs: Vec::new(),
+            halted: false,
+        });
+        self.machines.len() - 1
+    }
+
+    /// Routes the outputs of the first machine to the input of the second one.
+    ///
+    /// A machine can be connected to several machines, each getting all of its outputs.
+    ///
+    /// # Panics
+    /// Panics if either of the machines does not exist.
+    pub fn connect(&mut self, from: usize, to: usize) {
+        assert!(to < self.machines.len(), "Unknown machine: {}", to);
+        self.machines[from].routes.push(to);
+    }
+
+    /// Queues the value as an input of the given machine.
+    ///
+    /// # Panics
+    /// Panics if the machine does not exist.
+    pub fn push_input(&mut self, machine: usize, value: W) {
+        self.machines[machine].computer.push_input(value);
+    }
+
+    /// Returns the number of machines in the network.
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    /// Returns `true` if the network has no machines.
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    /// Returns `true` if the given machine terminated.
+    ///
+    /// # Panics
+    /// Panics if the machine does not exist.
+    pub fn is_halted(&self, machine: usize) -> bool {
+        self.machines[machine].halted
+    }
+
+    /// Returns all of the values the given machine outputted so far.
+    ///
+    /// # Panics
+    /// Panics if the machine does not exist.
+    pub fn outputs(&self, machine: usize) -> &[W] {
+        &self.machines[machine].outputs
+    }
+
+    /// Runs the machines until all of them terminate, they deadlock or one of them fails.
+    ///
+    /// After a deadlock the network can be resumed by pushing inputs and running it again.
+    pub fn run(&mut self) -> NetworkHalt<W> {
+        loop {
+            let mut progress = false;
+            let mut waiting = Vec::new();
+            for id in 0..self.machines.len() {
+                if self.machines[id].halted {
+                    continue;
+                }
+                match self.turn(id) {
+                    Turn::Progress => progress = true,
+                    Turn::Waiting => waiting.push(id),
+                    Turn::Error(error) => return NetworkHalt::Error { machine: id, error },
+                }
+            }
+
+            if waiting.is_empty() && self.machines.iter().all(|machine| machine.halted) {
+                return NetworkHalt::Halted;
+            }
+            if !progress {
+                return NetworkHalt::Deadlock(waiting);
+            }
+        }
+    }
+
+    // helper function to run a single turn of a machine, delivering its outputs
+    fn turn(&mut self, id: usize) -> Turn<W> {
+        let machine = &mut self.machines[id];
+        let started = machine.computer.instructions_executed();
+        let mut sent = Vec::new();
+        let mut turn = Turn::Progress;
+        for _ in 0..QUANTUM {
+            match machine.computer.step() {
+                // the machines of a network have no watchpoints
+                None | Some(HaltReason::Watchpoint(_)) => {}
+                Some(HaltReason::Output(value)) => sent.push(value),
+                Some(HaltReason::NeedsInput) => {
+                    if machine.computer.instructions_executed() == started {
+                        turn = Turn::Waiting;
+                    }
+                    break;
+                }
+                Some(HaltReason::Halted) => {
+                    machine.halted = true;
+                    break;
+                }
+                Some(HaltReason::LimitExceeded(limit)) => {
+                    let computer = &machine.computer;
+                    turn = Turn::Error(computer.fault_at_pc(Fault::LimitExceeded(limit)));
+                    break;
+                }
+                Some(HaltReason::Error(error)) => {
+                    turn = Turn::Error(error);
+                    break;
+                }
+            }
+        }
+
+        let routes = machine.routes.clone();
+        machine.outputs.extend(sent.iter().cloned());
+        for value in sent {
+            for &route in &routes {
+                self.machines[route].computer.push_input(value.clone());
+            }
+        }
+        turn
+    }
+}
+
+impl<W: Word> Default for Network<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
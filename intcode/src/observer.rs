@@ -0,0 +1,165 @@
+use crate::Word;
+use std::io::{self, Write};
+
+/// A hook that the [`IntcodeComputer`] notifies about every step of the execution.
+///
+/// All the methods do nothing by default, so an observer only needs to implement
+/// the events it is interested in.
+/// The computer is generic over its observer, and `()` (the default) observes nothing:
+/// since `()` sets `ENABLED` to `false`, the notifications are compiled out entirely.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+/// use std::io;
+///
+/// #[derive(Default)]
+/// struct OutputCounter(usize);
+///
+/// impl Observer<i64> for OutputCounter {
+///     fn on_output(&mut self, _value: &i64) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let memory = vec![104, 1, 104, 2, 99];
+/// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout())
+///     .with_observer(OutputCounter::default());
+///
+/// computer.run().unwrap();
+/// assert_eq!(computer.observer().0, 2);
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+pub trait Observer<W: Word> {
+    /// Whether the computer should notify the observer at all.
+    const ENABLED: bool = true;
+
+    /// Called when the instruction at `pc` is fetched, before it is executed.
+    fn on_fetch(&mut self, _pc: usize, _instruction: &W) {}
+
+    /// Called when a parameter in *position* or *relative* mode reads a memory location.
+    fn on_read(&mut self, _address: usize, _value: &W) {}
+
+    /// Called when an instruction writes a memory location.
+    fn on_write(&mut self, _address: usize, _old: &W, _new: &W) {}
+
+    /// Called when an `Input` (`3`) instruction consumes a value.
+    fn on_input(&mut self, _value: &W) {}
+
+    /// Called when an `Output` (`4`) instruction outputs a value.
+    fn on_output(&mut self, _value: &W) {}
+}
+
+impl<W: Word> Observer<W> for () {
+    const ENABLED: bool = false;
+}
+
+/// The format of the trace written by a [`Tracer`].
+///
+/// [`Tracer`]: struct.Tracer.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// A line per event, e.g. `write addr=4 old=33 new=99`.
+    Text,
+    /// A JSON object per line, e.g. `{"event":"write","addr":4,"old":33,"new":99}`.
+    JsonLines,
+}
+
+/// An [`Observer`] that writes a trace of every event to a `Write`.
+///
+/// Traces of two revisions of a program can be diffed to find where their behaviour diverges.
+/// Writing stops at the first I/O error, which is returned by `into_inner`.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+/// use std::io;
+///
+/// let memory = vec![1001, 5, 1, 5, 99, 41];
+/// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout())
+///     .with_observer(Tracer::new(Vec::new(), TraceFormat::Text));
+/// computer.run().unwrap();
+///
+/// let trace = computer.into_observer().into_inner().unwrap();
+/// assert_eq!(
+///     String::from_utf8(trace).unwrap(),
+///     "fetch pc=0 instruction=1001\n\
+///      read addr=5 value=41\n\
+///      write addr=5 old=41 new=42\n\
+///      fetch pc=4 instruction=99\n"
+/// );
+/// ```
+///
+/// [`Observer`]: trait.Observer.html
+#[derive(Debug)]
+pub struct Tracer<T: Write> {
+    writer: T,
+    format: TraceFormat,
+    error: Option<io::Error>,
+}
+
+impl<T: Write> Tracer<T> {
+    /// Creates a new tracer that writes the trace in the given format.
+    pub fn new(writer: T, format: TraceFormat) -> Self {
+        Self {
+            writer,
+            format,
+            error: None,
+        }
+    }
+
+    /// Returns the underlying writer, or the first error that occurred while writing the trace.
+    pub fn into_inner(mut self) -> io::Result<T> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => self.writer.flush().map(|_| self.writer),
+        }
+    }
+
+    // helper function to write a single event, formatted from its name and fields
+    fn event(&mut self, event: &str, fields: &[(&str, &dyn ToString)]) {
+        if self.error.is_some() {
+            return;
+        }
+        let line = match self.format {
+            TraceFormat::Text => fields
+                .iter()
+                .fold(String::from(event), |line, (key, value)| {
+                    format!("{} {}={}", line, key, value.to_string())
+                }),
+            TraceFormat::JsonLines => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, value)| format!(",\"{}\":{}", key, value.to_string()))
+                    .collect::<String>();
+                format!("{{\"event\":\"{}\"{}}}", event, fields)
+            }
+        };
+        if let Err(err) = writeln!(self.writer, "{}", line) {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl<W: Word, T: Write> Observer<W> for Tracer<T> {
+    fn on_fetch(&mut self, pc: usize, instruction: &W) {
+        self.event("fetch", &[("pc", &pc), ("instruction", instruction)]);
+    }
+
+    fn on_read(&mut self, address: usize, value: &W) {
+        self.event("read", &[("addr", &address), ("value", value)]);
+    }
+
+    fn on_write(&mut self, address: usize, old: &W, new: &W) {
+        self.event("write", &[("addr", &address), ("old", old), ("new", new)]);
+    }
+
+    fn on_input(&mut self, value: &W) {
+        self.event("input", &[("value", value)]);
+    }
+
+    fn on_output(&mut self, value: &W) {
+        self.event("output", &[("value", value)]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::Word;
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// An error which can occour while parsing a [`Patch`].
+///
+/// The error points to the line (starting at 1) that caused it.
+///
+/// [`Patch`]: struct.Patch.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchError {
+    /// The line at which the error occurred.
+    pub line: usize,
+    msg: String,
+}
+
+impl PatchError {
+    fn new(line: usize, msg: impl Into<String>) -> Self {
+        Self {
+            line,
+            msg: msg.into(),
+        }
+    }
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.line, self.msg)
+    }
+}
+
+impl error::Error for PatchError {}
+
+/// A list of writes to the memory of an [`IntcodeComputer`], applied with `apply_patch`.
+///
+/// # Syntax
+/// Each line holds a location, followed by `=` and comma-separated values,
+/// which are written to consecutive locations starting at the given one.
+/// Everything after a `#` is a comment.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+/// use std::io;
+///
+/// let patch: Patch = "# noun and verb\n1 = 5, 6".parse().unwrap();
+/// assert_eq!(patch.writes().collect::<Vec<_>>(), vec![(1, &5), (2, &6)]);
+///
+/// let memory = vec![1, 0, 0, 0, 99, 20, 22];
+/// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+/// computer.apply_patch(&patch).unwrap();
+/// assert_eq!(computer.run().unwrap().result, 42);
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Patch<W: Word = i64> {
+    writes: Vec<(usize, W)>,
+}
+
+impl<W: Word> Patch<W> {
+    /// Creates an empty patch.
+    pub fn new() -> Self {
+        Self { writes: Vec::new() }
+    }
+
+    /// Adds a write of the value to the given location.
+    ///
+    /// The writes are applied in the order they were added, so a later write to a location wins.
+    pub fn set(&mut self, address: usize, value: W) {
+        self.writes.push((address, value));
+    }
+
+    /// Returns the writes of the patch, in the order they are applied.
+    pub fn writes(&self) -> impl Iterator<Item = (usize, &W)> {
+        self.writes.iter().map(|(address, value)| (*address, value))
+    }
+
+    /// Returns `true` if the patch has no writes.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+}
+
+impl<W: Word> Default for Patch<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Word> FromStr for Patch<W> {
+    type Err = PatchError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut patch = Self::new();
+        for (idx, line) in source.lines().enumerate() {
+            let line_number = idx + 1;
+            let text = line.split('#').next().unwrap_or("").trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let (address, values) = text
+                .split_once('=')
+                .ok_or_else(|| PatchError::new(line_number, "expected `location = values`"))?;
+            let address = address.trim();
+            let address = address.parse::<usize>().map_err(|_| {
+                PatchError::new(line_number, format!("invalid location `{}`", address))
+            })?;
+
+            for (offset, value) in values.split(',').enumerate() {
+                let value = value.trim();
+                let value = value.parse::<W>().map_err(|_| {
+                    PatchError::new(line_number, format!("invalid value `{}`", value))
+                })?;
+                let target = address
+                    .checked_add(offset)
+                    .ok_or_else(|| PatchError::new(line_number, "location out of range"))?;
+                patch.set(target, value);
+            }
+        }
+
+        Ok(patch)
+    }
+}
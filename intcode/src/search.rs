@@ -0,0 +1,404 @@
+use crate::{compile, CompiledProgram, IntcodeComputer, Limits, Memory, Snapshot, Word};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    convert::TryFrom,
+    error,
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+/// The number of candidates a thread of a [`Search`] takes at a time.
+///
+/// [`Search`]: struct.Search.html
+const CHUNK_SIZE: u64 = 64;
+
+/// A value that a [`Search`] varies.
+///
+/// [`Search`]: struct.Search.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchVariable {
+    /// The memory cell at the given location, set before the program runs.
+    Memory(usize),
+    /// The input at the given position (the first input the program reads is `0`).
+    Input(usize),
+}
+
+impl Display for SearchVariable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SearchVariable::Memory(address) => write!(f, "memory[{}]", address),
+            SearchVariable::Input(position) => write!(f, "input[{}]", position),
+        }
+    }
+}
+
+/// Which of the matching candidates a [`Search`] returns.
+///
+/// [`Search`]: struct.Search.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchMode {
+    /// All of the matches, in the order the candidates are enumerated.
+    All,
+    /// The first match, in the order the candidates are enumerated.
+    First,
+    /// The match with the smallest key (the first one, if several have it).
+    Min,
+    /// The match with the largest key (the first one, if several have it).
+    Max,
+}
+
+/// A candidate that ran to completion, passed to the objective of a [`Search`].
+///
+/// [`Search`]: struct.Search.html
+#[derive(Debug)]
+pub struct Candidate<'a, W: Word> {
+    /// The values of the variables, in the order they were added to the search.
+    pub values: &'a [i64],
+    /// The memory at the end of the run.
+    pub memory: &'a Memory<W>,
+    /// The values the program outputted.
+    pub outputs: &'a [W],
+}
+
+/// A candidate that matched the objective of a [`Search`].
+///
+/// [`Search`]: struct.Search.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchMatch<K> {
+    /// The values of the variables, in the order they were added to the search.
+    pub values: Vec<i64>,
+    /// The key the objective returned for the candidate.
+    pub key: K,
+}
+
+/// An error which can occour when running a [`Search`].
+///
+/// [`Search`]: struct.Search.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchError {
+    /// The number of candidates does not fit in a `u64`.
+    TooManyCandidates,
+    /// Some of the values of the variable don't fit in the word of the computer.
+    OutOfRange {
+        /// The variable whose values don't fit.
+        variable: SearchVariable,
+        /// The values of the variable.
+        values: RangeInclusive<i64>,
+    },
+    /// The input at the given position isn't varied, although a later one is,
+    /// so the later inputs could never be read.
+    MissingInput {
+        /// The position of the missing input.
+        position: usize,
+    },
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SearchError::TooManyCandidates => write!(f, "There are too many candidates to search"),
+            SearchError::OutOfRange { variable, values } => write!(
+                f,
+                "The values of {} ({}..={}) don't fit in a word",
+                variable,
+                values.start(),
+                values.end()
+            ),
+            SearchError::MissingInput { position } => {
+                write!(f, "input[{}] is not varied, but a later input is", position)
+            }
+        }
+    }
+}
+
+impl error::Error for SearchError {}
+
+/// A brute-force search over the values of memory cells and inputs, run in parallel.
+///
+/// Each combination of the values of the variables (a *candidate*) is run on its own
+/// [`IntcodeComputer`], with the program compiled once for all of them (see `run_compiled`). The candidates are enumerated with the first variable varying slowest.
+/// Candidates that fail, need more input than they were given or exceed the limits are skipped,
+/// and the others are passed to the objective, which returns a key for the matching ones.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// // outputs input[0] * memory[9]
+/// let memory = vec![3, 10, 2, 9, 10, 10, 4, 10, 99, 0, 0];
+/// let mut search = Search::new(memory);
+/// search.vary(SearchVariable::Input(0), 0..=10);
+/// search.vary(SearchVariable::Memory(9), -5..=5);
+///
+/// let output = |candidate: &Candidate<i64>| candidate.outputs.first().copied();
+/// let twelve = |candidate: &Candidate<i64>| output(candidate).filter(|&output| output == 12);
+///
+/// let matches = search.run(twelve).unwrap();
+/// assert_eq!(matches, vec![SearchMatch { values: vec![3, 4], key: 12 }]);
+///
+/// search.set_mode(SearchMode::All);
+/// assert_eq!(search.run(twelve).unwrap().len(), 3);
+///
+/// search.set_mode(SearchMode::Min);
+/// let min = search.run(output).unwrap();
+/// assert_eq!(min, vec![SearchMatch { values: vec![10, -5], key: -50 }]);
+///
+/// search.vary(SearchVariable::Input(1), i64::MIN..=i64::MAX);
+/// assert_eq!(search.run(output), Err(SearchError::TooManyCandidates));
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Clone, Debug)]
+pub struct Search<W: Word = i64> {
+    memory: Vec<W>,
+    variables: Vec<(SearchVariable, RangeInclusive<i64>)>,
+    mode: SearchMode,
+    limits: Limits,
+    threads: usize,
+}
+
+impl<W: Word + Send + Sync> Search<W> {
+    /// Creates a search over the given program, without any variables.
+    ///
+    /// The search returns the first match, runs without limits and uses all of the cores.
+    pub fn new(memory: Vec<W>) -> Self {
+        Self {
+            memory,
+            variables: Vec::new(),
+            mode: SearchMode::First,
+            limits: Limits::default(),
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        }
+    }
+
+    /// Adds a variable, which takes each of the given values.
+    pub fn vary(&mut self, variable: SearchVariable, values: RangeInclusive<i64>) {
+        self.variables.push((variable, values));
+    }
+
+    /// Returns the variables of the search.
+    pub fn variables(&self) -> impl Iterator<Item = &SearchVariable> {
+        self.variables.iter().map(|(variable, _)| variable)
+    }
+
+    /// Sets which of the matches the search returns.
+    pub fn set_mode(&mut self, mode: SearchMode) {
+        self.mode = mode;
+    }
+
+    /// Sets the limits of each run, e.g. to skip candidates that loop forever.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Sets the number of threads that run the candidates.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Returns the number of candidates, or `None` if it does not fit in a `u64`.
+    pub fn candidates(&self) -> Option<u64> {
+        self.variables.iter().try_fold(1u64, |count, (_, values)| {
+            count.checked_mul(range_len(values)?)
+        })
+    }
+
+    /// Runs the search, returning the matches selected by the mode.
+    ///
+    /// The objective returns the key of a matching candidate, or `None` if it does not match.
+    /// Modes other than [`SearchMode::All`] return at most one match.
+    ///
+    /// Fails without running any candidate if the number of candidates does not fit in a `u64`,
+    /// if the values of a variable don't fit in the word `W`, or if the varied inputs
+    /// skip a position.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    ///
+    /// let mut search: Search<i32> = Search::new(vec![1, 0, 0, 0, 99]);
+    /// search.vary(SearchVariable::Memory(1), 0..=5_000_000_000);
+    ///
+    /// let error = search.run(|_| Some(())).unwrap_err();
+    /// assert!(matches!(error, SearchError::OutOfRange { .. }));
+    ///
+    /// let mut search: Search<i64> = Search::new(vec![3, 0, 3, 0, 99]);
+    /// search.vary(SearchVariable::Input(0), 0..=1);
+    /// search.vary(SearchVariable::Input(2), 0..=1);
+    ///
+    /// let error = search.run(|_| Some(())).unwrap_err();
+    /// assert_eq!(error, SearchError::MissingInput { position: 1 });
+    /// ```
+    ///
+    /// [`SearchMode::All`]: enum.SearchMode.html#variant.All
+    pub fn run<K, F>(&self, objective: F) -> Result<Vec<SearchMatch<K>>, SearchError>
+    where
+        K: Ord + Send,
+        F: Fn(&Candidate<W>) -> Option<K> + Sync,
+    {
+        let total = self.candidates().ok_or(SearchError::TooManyCandidates)?;
+        // a range fits in a word if both of its ends do
+        for (variable, values) in &self.variables {
+            let fits = |value: i64| W::checked_from_i64(value).is_some();
+            let all_fit = values.is_empty() || (fits(*values.start()) && fits(*values.end()));
+            if !all_fit {
+                return Err(SearchError::OutOfRange {
+                    variable: *variable,
+                    values: values.clone(),
+                });
+            }
+        }
+        // the inputs are queued in order, so they must not skip a position
+        let positions = self
+            .variables
+            .iter()
+            .filter_map(|(variable, _)| match variable {
+                SearchVariable::Input(position) => Some(*position),
+                SearchVariable::Memory(_) => None,
+            })
+            .collect::<BTreeSet<usize>>();
+        let missing = (0..positions.len()).find(|position| !positions.contains(position));
+        if let Some(position) = missing {
+            return Err(SearchError::MissingInput { position });
+        }
+        let program = compile(&self.memory);
+        let snapshot =
+            IntcodeComputer::new(self.memory.clone(), VecDeque::new(), Vec::new()).snapshot();
+        let next = AtomicU64::new(0);
+        // the index of the first match, which ends a search for the first match early
+        let first = AtomicU64::new(u64::MAX);
+
+        let mut found = thread::scope(|scope| {
+            let workers = (0..self.threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut found = Vec::new();
+                        loop {
+                            let start = next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                            if start >= total.min(first.load(Ordering::Relaxed)) {
+                                break found;
+                            }
+                            for idx in start..total.min(start.saturating_add(CHUNK_SIZE)) {
+                                if idx > first.load(Ordering::Relaxed) {
+                                    break;
+                                }
+                                let key = match self
+                                    .run_candidate(&program, &snapshot, idx, &objective)
+                                {
+                                    Some(key) => key,
+                                    None => continue,
+                                };
+                                if self.mode == SearchMode::First {
+                                    first.fetch_min(idx, Ordering::Relaxed);
+                                }
+                                self.keep(&mut found, (idx, key));
+                            }
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut found = Vec::new();
+            for worker in workers {
+                for candidate in worker.join().expect("A search thread panicked") {
+                    self.keep(&mut found, candidate);
+                }
+            }
+            found
+        });
+
+        found.sort_by_key(|(idx, _)| *idx);
+        Ok(found
+            .into_iter()
+            .map(|(idx, key)| SearchMatch {
+                values: self.values(idx),
+                key,
+            })
+            .collect())
+    }
+
+    // helper function to add a match to the matches kept so far, according to the mode
+    fn keep<K: Ord>(&self, found: &mut Vec<(u64, K)>, candidate: (u64, K)) {
+        let better = match (found.first(), self.mode) {
+            (_, SearchMode::All) => {
+                found.push(candidate);
+                return;
+            }
+            (None, _) => true,
+            (Some(best), SearchMode::First) => candidate.0 < best.0,
+            (Some(best), SearchMode::Min) => {
+                candidate.1 < best.1 || (candidate.1 == best.1 && candidate.0 < best.0)
+            }
+            (Some(best), SearchMode::Max) => {
+                candidate.1 > best.1 || (candidate.1 == best.1 && candidate.0 < best.0)
+            }
+        };
+        if better {
+            found.clear();
+            found.push(candidate);
+        }
+    }
+
+    // helper function to get the values of the variables of a candidate
+    fn values(&self, mut idx: u64) -> Vec<i64> {
+        let mut values = vec![0; self.variables.len()];
+        for (value, (_, range)) in values.iter_mut().zip(&self.variables).rev() {
+            let len = range_len(range).unwrap_or(u64::MAX);
+            *value = range.start().wrapping_add((idx % len) as i64);
+            idx /= len;
+        }
+        values
+    }
+
+    // helper function to run a candidate, returning its key if it matches
+    fn run_candidate<K, F>(
+        &self,
+        program: &CompiledProgram<W>,
+        snapshot: &Snapshot<W>,
+        idx: u64,
+        objective: &F,
+    ) -> Option<K>
+    where
+        F: Fn(&Candidate<W>) -> Option<K>,
+    {
+        let values = self.values(idx);
+        // the outputs are collected by the report of the run
+        let mut computer =
+            IntcodeComputer::from_snapshot(snapshot.clone(), VecDeque::new(), |_: W| {});
+        computer.set_limits(self.limits);
+        let mut inputs = BTreeMap::new();
+        for ((variable, _), value) in self.variables.iter().zip(&values) {
+            match variable {
+                SearchVariable::Memory(address) => computer
+                    .memory_mut()
+                    .set(*address, W::from_i64(*value))
+                    .ok()?,
+                SearchVariable::Input(position) => {
+                    inputs.insert(*position, *value);
+                }
+            }
+        }
+        // the positions of the inputs are contiguous (see `run`)
+        for value in inputs.into_values() {
+            computer.push_input(W::from_i64(value));
+        }
+
+        // running out of the queued inputs fails the run, like any other error
+        let report = computer.run_compiled(program).ok()?;
+        objective(&Candidate {
+            values: &values,
+            memory: &report.memory,
+            outputs: &report.outputs,
+        })
+    }
+}
+
+// helper function to get the number of values in a range
+fn range_len(range: &RangeInclusive<i64>) -> Option<u64> {
+    if range.is_empty() {
+        return Some(0);
+    }
+    let len = i128::from(*range.end()) - i128::from(*range.start()) + 1;
+    u64::try_from(len).ok()
+}
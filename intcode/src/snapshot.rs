@@ -0,0 +1,169 @@
+use crate::{Memory, Word};
+use std::collections::VecDeque;
+#[cfg(feature = "serde")]
+use {
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    std::{
+        error,
+        fmt::{self, Display, Formatter},
+        io::{self, Read, Write},
+    },
+};
+
+/// The version of the snapshot file format, written by `Snapshot::save`.
+#[cfg(feature = "serde")]
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The full execution state of an [`IntcodeComputer`].
+///
+/// A snapshot is taken with `IntcodeComputer::snapshot`, and can later be restored
+/// with `IntcodeComputer::restore` (or `IntcodeComputer::from_snapshot`) to resume the execution.
+/// Enabling the `serde` feature allows saving snapshots to a file and loading them back,
+/// so a program can be suspended in one process and resumed in another.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+/// use std::io;
+///
+/// let memory = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+/// let mut computer = IntcodeComputer::new(memory, io::stdin(), io::stdout());
+/// assert!(matches!(computer.run_until_io(), HaltReason::NeedsInput));
+/// let checkpoint = computer.snapshot();
+///
+/// computer.push_input(41);
+/// assert!(matches!(computer.run_until_io(), HaltReason::Output(42)));
+///
+/// computer.restore(&checkpoint);
+/// computer.push_input(1);
+/// assert!(matches!(computer.run_until_io(), HaltReason::Output(2)));
+/// ```
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+#[derive(Clone, Debug)]
+pub struct Snapshot<W: Word> {
+    /// The memory of the computer.
+    pub memory: Memory<W>,
+    /// The program counter of the computer.
+    pub pc: usize,
+    /// The relative base of the computer.
+    pub relative_base: i64,
+    /// The input values that were queued but not consumed yet.
+    pub pending_input: VecDeque<W>,
+}
+
+#[cfg(feature = "serde")]
+impl<W: Word + Serialize + DeserializeOwned> Snapshot<W> {
+    /// Saves the snapshot to the given writer, using a versioned JSON format.
+    ///
+    /// The limit of the memory is not saved.
+    ///
+    /// # Example
+    /// ```
+    /// use intcode::*;
+    ///
+    /// let mut memory = Memory::new(vec![104, 7, 99]);
+    /// memory.set(1_000_000, 3).unwrap();
+    /// let snapshot = Snapshot {
+    ///     memory,
+    ///     pc: 2,
+    ///     relative_base: -4,
+    ///     pending_input: vec![5].into(),
+    /// };
+    ///
+    /// let mut file = Vec::new();
+    /// snapshot.save(&mut file).unwrap();
+    /// let loaded = Snapshot::<i64>::load(file.as_slice()).unwrap();
+    ///
+    /// assert_eq!((loaded.pc, loaded.relative_base), (2, -4));
+    /// assert_eq!(*loaded.memory.get(1_000_000).unwrap(), 3);
+    /// assert_eq!(loaded.pending_input, snapshot.pending_input);
+    /// ```
+    pub fn save<T: Write>(&self, writer: T) -> Result<(), SnapshotError> {
+        let (dense, sparse) = self.memory.to_parts();
+        let file = SnapshotFile {
+            version: SNAPSHOT_VERSION,
+            pc: self.pc,
+            relative_base: self.relative_base,
+            memory: dense,
+            sparse,
+            pending_input: self.pending_input.iter().cloned().collect(),
+        };
+        serde_json::to_writer(writer, &file)?;
+        Ok(())
+    }
+
+    /// Loads a snapshot that was saved with `save` from the given reader.
+    pub fn load<R: Read>(reader: R) -> Result<Self, SnapshotError> {
+        let file: SnapshotFile<W> = serde_json::from_reader(reader)?;
+        if file.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(file.version));
+        }
+        Ok(Self {
+            memory: Memory::from_parts(file.memory, file.sparse),
+            pc: file.pc,
+            relative_base: file.relative_base,
+            pending_input: file.pending_input.into(),
+        })
+    }
+}
+
+/// The layout of a saved snapshot.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile<W> {
+    version: u32,
+    pc: usize,
+    relative_base: i64,
+    memory: Vec<W>,
+    sparse: Vec<(usize, W)>,
+    pending_input: Vec<W>,
+}
+
+/// An error which can occour while saving or loading a [`Snapshot`].
+///
+/// [`Snapshot`]: struct.Snapshot.html
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The snapshot could not be read or written.
+    Io(io::Error),
+    /// The snapshot is not a valid snapshot file.
+    Format(serde_json::Error),
+    /// The snapshot was saved with an unsupported version of the file format.
+    UnsupportedVersion(u32),
+}
+
+#[cfg(feature = "serde")]
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "Could not access the snapshot: {}", err),
+            SnapshotError::Format(err) => write!(f, "Invalid snapshot: {}", err),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported snapshot version {}", version)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(err) => Some(err),
+            SnapshotError::Format(err) => Some(err),
+            SnapshotError::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for SnapshotError {
+    fn from(err: serde_json::Error) -> Self {
+        match err.classify() {
+            serde_json::error::Category::Io => SnapshotError::Io(err.into()),
+            _ => SnapshotError::Format(err),
+        }
+    }
+}
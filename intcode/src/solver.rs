@@ -0,0 +1,424 @@
+use crate::{Constraint, Linear, Relation, Symbol};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+
+/// The maximal number of assignments tried by `solve`.
+const SEARCH_LIMIT: u64 = 100_000;
+
+/// The maximal number of assignments tried when checking whether a path is feasible.
+pub(crate) const FEASIBILITY_LIMIT: u64 = 1_000;
+
+/// The maximal number of rounds of bound propagation between two assignments.
+const PROPAGATION_ROUNDS: usize = 64;
+
+/// Values for the symbols of a set of [`Constraint`]s that satisfy all of them.
+///
+/// [`Constraint`]: struct.Constraint.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    values: BTreeMap<Symbol, i64>,
+    exact: bool,
+}
+
+impl Solution {
+    /// Returns the value of the given symbol.
+    ///
+    /// Symbols that are not part of any of the constraints have no value: any value will do.
+    pub fn value(&self, symbol: &Symbol) -> Option<i64> {
+        self.values.get(symbol).copied()
+    }
+
+    /// Returns the values of the symbols, ordered by the time the symbols were created.
+    pub fn values(&self) -> impl Iterator<Item = (&Symbol, i64)> {
+        self.values.iter().map(|(symbol, value)| (symbol, *value))
+    }
+
+    /// Returns `true` if none of the constraints contain an *approximate* symbol.
+    ///
+    /// Approximate symbols stand for values the symbolic execution could not follow
+    /// (see [`Symbol::is_approximate`]), so an inexact solution may not hold for a real run.
+    ///
+    /// [`Symbol::is_approximate`]: struct.Symbol.html#method.is_approximate
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (idx, (symbol, value)) in self.values().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} = {}", symbol, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of solving a set of [`Constraint`]s.
+///
+/// [`Constraint`]: struct.Constraint.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolverResult {
+    /// The constraints hold for the given values.
+    Satisfiable(Solution),
+    /// No values satisfy all of the constraints.
+    Unsatisfiable,
+    /// The solver gave up, e.g. because the search space was too large.
+    Unknown,
+}
+
+/// Solves a set of linear [`Constraint`]s over the integers.
+///
+/// The equalities are eliminated first, by solving them for one of their symbols (after
+/// reducing their coefficients, as in Euclid's algorithm). The remaining inequalities
+/// are solved by a search that narrows the bounds of the symbols, trying the values closest
+/// to zero first when a symbol is unbounded. The search is limited, so the solver may
+/// give up with [`SolverResult::Unknown`].
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// let mut computer = SymbolicComputer::new(&[0i64]);
+/// let (x, y) = (computer.symbol("x"), computer.symbol("y"));
+/// let sum = Linear::from(&x).checked_add(&Linear::from(&y)).unwrap();
+///
+/// let constraints = vec![
+///     Constraint::equal(sum, 10),
+///     Constraint::greater(&x, 7),
+///     Constraint::not_equal(&y, 1),
+/// ];
+/// match solve(&constraints) {
+///     SolverResult::Satisfiable(solution) => {
+///         let (x, y) = (solution.value(&x).unwrap(), solution.value(&y).unwrap());
+///         assert_eq!(x + y, 10);
+///         assert!(x > 7 && y != 1);
+///     }
+///     result => panic!("{:?}", result),
+/// }
+///
+/// let constraints = vec![Constraint::equal(Linear::from(&x).checked_mul(2).unwrap(), 7)];
+/// assert_eq!(solve(&constraints), SolverResult::Unsatisfiable);
+/// ```
+///
+/// [`Constraint`]: struct.Constraint.html
+/// [`SolverResult::Unknown`]: enum.SolverResult.html#variant.Unknown
+pub fn solve(constraints: &[Constraint]) -> SolverResult {
+    solve_with_limit(constraints, SEARCH_LIMIT)
+}
+
+/// Solves the constraints, trying at most `limit` assignments.
+pub(crate) fn solve_with_limit(constraints: &[Constraint], limit: u64) -> SolverResult {
+    // arithmetic overflow makes the solver give up
+    try_solve(constraints, limit).unwrap_or(SolverResult::Unknown)
+}
+
+fn try_solve(constraints: &[Constraint], limit: u64) -> Option<SolverResult> {
+    let mut next_id = constraints
+        .iter()
+        .flat_map(|constraint| constraint.expr.symbols())
+        .map(|symbol| symbol.id() + 1)
+        .max()
+        .unwrap_or(0);
+
+    // eliminate the equalities, remembering the substitutions to recover the values later
+    let mut remaining = constraints.to_vec();
+    let mut substitutions: Vec<(Symbol, Linear)> = Vec::new();
+    while let Some(idx) = remaining
+        .iter()
+        .position(|constraint| constraint.relation == Relation::Zero)
+    {
+        let expr = remaining.swap_remove(idx).expr;
+        let divisor = expr.coefficients().fold(0, gcd);
+        if divisor == 0 {
+            if expr.offset() != 0 {
+                return Some(SolverResult::Unsatisfiable);
+            }
+            continue;
+        }
+        if expr.offset() % divisor != 0 {
+            return Some(SolverResult::Unsatisfiable);
+        }
+        let expr = expr.checked_div(divisor)?;
+
+        let unit = expr
+            .terms()
+            .find(|(_, coefficient)| coefficient.abs() == 1)
+            .map(|(symbol, coefficient)| (symbol.clone(), coefficient));
+        let (symbol, value) = match unit {
+            Some((symbol, coefficient)) => {
+                // x = -(rest) when the coefficient is 1, and x = rest when it is -1
+                let rest = expr.substitute(&symbol, &Linear::default())?;
+                let value = rest.checked_scale(-coefficient)?;
+                (symbol, value)
+            }
+            None => {
+                // substitute x = y - sum(floor(b / a) * z) for the symbol x with the smallest
+                // coefficient a, leaving the remainders b mod a as the other coefficients
+                let (symbol, coefficient) = expr
+                    .terms()
+                    .min_by_key(|(_, coefficient)| coefficient.abs())
+                    .map(|(symbol, coefficient)| (symbol.clone(), coefficient))?;
+                let fresh = Symbol::internal(next_id);
+                next_id += 1;
+                let mut value = Linear::from(&fresh);
+                for (other, other_coefficient) in expr.terms() {
+                    if *other != symbol {
+                        let quotient = floor_div(other_coefficient, coefficient)?;
+                        let term = Linear::from(other).checked_scale(-quotient)?;
+                        value = value.checked_add(&term)?;
+                    }
+                }
+                remaining.push(Constraint {
+                    expr,
+                    relation: Relation::Zero,
+                });
+                (symbol, value)
+            }
+        };
+        for constraint in &mut remaining {
+            constraint.expr = constraint.expr.substitute(&symbol, &value)?;
+        }
+        substitutions.push((symbol, value));
+    }
+
+    // search values for the symbols of the remaining inequalities
+    let mut bounds = BTreeMap::new();
+    for constraint in &remaining {
+        for symbol in constraint.expr.symbols() {
+            bounds.insert(symbol.clone(), Bounds::default());
+        }
+    }
+    let mut search = Search {
+        constraints: &remaining,
+        tried: 0,
+        limit,
+        complete: true,
+    };
+    let mut values = match search.search(bounds)? {
+        Some(bounds) => bounds
+            .into_iter()
+            .map(|(symbol, bounds)| (symbol, bounds.low.unwrap_or(0)))
+            .collect::<BTreeMap<Symbol, i128>>(),
+        None if search.complete => return Some(SolverResult::Unsatisfiable),
+        None => return Some(SolverResult::Unknown),
+    };
+
+    // recover the values of the eliminated symbols, the symbols left free are zero
+    for (symbol, value) in substitutions.iter().rev() {
+        let value = value.evaluate_with(|symbol| values.get(symbol).copied().unwrap_or(0))?;
+        values.insert(symbol.clone(), value);
+    }
+
+    let mut solution = Solution {
+        values: BTreeMap::new(),
+        exact: true,
+    };
+    for constraint in constraints {
+        let value = constraint
+            .expr
+            .evaluate_with(|symbol| values.get(symbol).copied().unwrap_or(0))?;
+        if !constraint.relation.holds(value) {
+            // not expected, but a wrong answer is worse than no answer
+            return Some(SolverResult::Unknown);
+        }
+        for symbol in constraint.expr.symbols() {
+            let value = values.get(symbol).copied().unwrap_or(0);
+            solution
+                .values
+                .insert(symbol.clone(), i64::try_from(value).ok()?);
+            solution.exact &= !symbol.is_approximate();
+        }
+    }
+    Some(SolverResult::Satisfiable(solution))
+}
+
+/// The known bounds of a symbol, both inclusive.
+#[derive(Clone, Copy, Debug, Default)]
+struct Bounds {
+    low: Option<i128>,
+    high: Option<i128>,
+}
+
+impl Bounds {
+    fn is_fixed(&self) -> bool {
+        self.low.is_some() && self.low == self.high
+    }
+
+    // helper function to get the maximum of coefficient * symbol
+    fn max_of(&self, coefficient: i128) -> Option<i128> {
+        let bound = if coefficient > 0 { self.high } else { self.low };
+        bound?.checked_mul(coefficient)
+    }
+}
+
+struct Search<'a> {
+    constraints: &'a [Constraint],
+    tried: u64,
+    limit: u64,
+    complete: bool,
+}
+
+impl<'a> Search<'a> {
+    /// Searches values within the bounds, returning the bounds of a solution (where each
+    /// symbol is fixed), or `None` if there is none. Returns `None` on overflow.
+    fn search(
+        &mut self,
+        mut bounds: BTreeMap<Symbol, Bounds>,
+    ) -> Option<Option<BTreeMap<Symbol, Bounds>>> {
+        if !self.propagate(&mut bounds)? {
+            return Some(None);
+        }
+        // pick the symbol with the fewest possible values
+        let symbol = bounds
+            .iter()
+            .filter(|(_, bounds)| !bounds.is_fixed())
+            .min_by_key(|(_, bounds)| match (bounds.low, bounds.high) {
+                (Some(low), Some(high)) => (0, high - low),
+                (Some(_), None) | (None, Some(_)) => (1, 0),
+                (None, None) => (2, 0),
+            })
+            .map(|(symbol, bounds)| (symbol.clone(), *bounds));
+        let (symbol, range) = match symbol {
+            Some(symbol) => symbol,
+            None if self.check(&bounds)? => return Some(Some(bounds)),
+            None => return Some(None),
+        };
+
+        let mut step = 0i128;
+        loop {
+            let value = match (range.low, range.high) {
+                (Some(low), Some(high)) => match low.checked_add(step)? {
+                    value if value <= high => value,
+                    _ => return Some(None),
+                },
+                (Some(low), None) => low.checked_add(step)?,
+                (None, Some(high)) => high.checked_sub(step)?,
+                // 0, 1, -1, 2, -2, ...
+                (None, None) if step % 2 == 1 => (step + 1) / 2,
+                (None, None) => -(step / 2),
+            };
+            if self.tried >= self.limit {
+                self.complete = false;
+                return Some(None);
+            }
+            self.tried += 1;
+
+            let mut next = bounds.clone();
+            next.insert(
+                symbol.clone(),
+                Bounds {
+                    low: Some(value),
+                    high: Some(value),
+                },
+            );
+            if let Some(solution) = self.search(next)? {
+                return Some(Some(solution));
+            }
+            step += 1;
+        }
+    }
+
+    /// Narrows the bounds using the inequalities, returning `false` if a symbol has no
+    /// possible values left.
+    fn propagate(&self, bounds: &mut BTreeMap<Symbol, Bounds>) -> Option<bool> {
+        for _ in 0..PROPAGATION_ROUNDS {
+            let mut changed = false;
+            for constraint in self.constraints {
+                if constraint.relation != Relation::NonNegative {
+                    continue;
+                }
+                for (symbol, coefficient) in constraint.expr.terms() {
+                    // the maximum of the other terms, if they are bounded
+                    let mut rest = Some(constraint.expr.offset());
+                    for (other, other_coefficient) in constraint.expr.terms() {
+                        if other != symbol {
+                            let max = bounds[other].max_of(other_coefficient);
+                            rest = match (rest, max) {
+                                (Some(rest), Some(max)) => Some(rest.checked_add(max)?),
+                                _ => None,
+                            };
+                        }
+                    }
+                    let rest = match rest {
+                        Some(rest) => rest.checked_neg()?,
+                        None => continue,
+                    };
+                    // coefficient * symbol >= rest
+                    let bound = bounds.get_mut(symbol)?;
+                    if coefficient > 0 {
+                        let low = ceil_div(rest, coefficient)?;
+                        if bound.low.is_none_or(|current| low > current) {
+                            bound.low = Some(low);
+                            changed = true;
+                        }
+                    } else {
+                        let high = floor_div(rest, coefficient)?;
+                        if bound.high.is_none_or(|current| high < current) {
+                            bound.high = Some(high);
+                            changed = true;
+                        }
+                    }
+                    if let (Some(low), Some(high)) = (bound.low, bound.high) {
+                        if low > high {
+                            return Some(false);
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        Some(true)
+    }
+
+    /// Checks all constraints for the fixed values of the symbols.
+    fn check(&self, bounds: &BTreeMap<Symbol, Bounds>) -> Option<bool> {
+        for constraint in self.constraints {
+            let value = constraint
+                .expr
+                .evaluate_with(|symbol| bounds[symbol].low.unwrap_or(0))?;
+            if !constraint.relation.holds(value) {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+}
+
+// helper function to get the greatest common divisor
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+    a
+}
+
+// helper function to divide, rounding towards negative infinity
+fn floor_div(a: i128, b: i128) -> Option<i128> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        quotient.checked_sub(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+// helper function to divide, rounding towards positive infinity
+fn ceil_div(a: i128, b: i128) -> Option<i128> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && ((a < 0) == (b < 0)) {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
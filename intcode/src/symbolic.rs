@@ -0,0 +1,1047 @@
+use crate::{
+    solve,
+    solver::{solve_with_limit, FEASIBILITY_LIMIT},
+    Fault, IntcodeError, LimitExceeded, Limits, Memory, MemoryLimitExceeded, Opcode, ParameterMode,
+    Solution, SolverResult, Word, TIME_CHECK_INTERVAL,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, VecDeque},
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    sync::Arc,
+    time::Instant,
+};
+
+/// The default number of instructions each path of a [`SymbolicComputer`] may execute.
+///
+/// [`SymbolicComputer`]: struct.SymbolicComputer.html
+pub const DEFAULT_PATH_INSTRUCTIONS: u64 = 1_000_000;
+
+/// The default number of paths a [`SymbolicComputer`] explores.
+///
+/// [`SymbolicComputer`]: struct.SymbolicComputer.html
+pub const DEFAULT_PATH_LIMIT: usize = 1024;
+
+/// An unknown value of a symbolic execution, e.g. a memory cell or an input.
+///
+/// Symbols are created by a [`SymbolicComputer`], and are ordered by the time they were created.
+///
+/// [`SymbolicComputer`]: struct.SymbolicComputer.html
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    id: usize,
+    name: Arc<str>,
+    approximate: bool,
+}
+
+impl Symbol {
+    /// Creates a symbol that only the solver uses.
+    pub(crate) fn internal(id: usize) -> Self {
+        Self {
+            id,
+            name: Arc::from("_"),
+            approximate: false,
+        }
+    }
+
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the name of the symbol.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the symbol stands for a value the symbolic execution could not follow.
+    ///
+    /// Reading from a location that is itself symbolic (e.g. the parameters of the first
+    /// instruction of day 2) gives an approximate symbol, which may take any value.
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A linear combination of [`Symbol`]s plus a constant, e.g. `216000*noun + verb + 466644`.
+///
+/// The coefficients are unbounded in principle (they are `i128`s), the operations return `None`
+/// if they overflow.
+///
+/// [`Symbol`]: struct.Symbol.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Linear {
+    terms: BTreeMap<Symbol, i128>,
+    constant: i128,
+}
+
+impl Linear {
+    /// Returns the value of the expression, if it has no symbols.
+    pub fn as_constant(&self) -> Option<i128> {
+        if self.terms.is_empty() {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the constant part of the expression.
+    pub fn offset(&self) -> i128 {
+        self.constant
+    }
+
+    /// Returns the coefficient of the given symbol, which is zero for the symbols not in the expression.
+    pub fn coefficient(&self, symbol: &Symbol) -> i128 {
+        self.terms.get(symbol).copied().unwrap_or(0)
+    }
+
+    /// Returns the symbols of the expression.
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.terms.keys()
+    }
+
+    /// Returns the value of the expression for the given solution,
+    /// if the solution has a value for each of its symbols.
+    pub fn evaluate(&self, solution: &Solution) -> Option<i128> {
+        let mut value = self.constant;
+        for (symbol, coefficient) in self.terms() {
+            let term = coefficient.checked_mul(i128::from(solution.value(symbol)?))?;
+            value = value.checked_add(term)?;
+        }
+        Some(value)
+    }
+
+    /// Adds two expressions, returning `None` on overflow.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut sum = self.clone();
+        sum.constant = sum.constant.checked_add(other.constant)?;
+        for (symbol, coefficient) in &other.terms {
+            let sum_coefficient = sum.coefficient(symbol).checked_add(*coefficient)?;
+            if sum_coefficient == 0 {
+                sum.terms.remove(symbol);
+            } else {
+                sum.terms.insert(symbol.clone(), sum_coefficient);
+            }
+        }
+        Some(sum)
+    }
+
+    /// Subtracts two expressions, returning `None` on overflow.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.checked_scale(-1)?)
+    }
+
+    /// Multiplies the expression by a constant, returning `None` on overflow.
+    pub fn checked_mul(&self, factor: i64) -> Option<Self> {
+        self.checked_scale(i128::from(factor))
+    }
+
+    pub(crate) fn checked_scale(&self, factor: i128) -> Option<Self> {
+        if factor == 0 {
+            return Some(Self::default());
+        }
+        let mut product = Self {
+            terms: BTreeMap::new(),
+            constant: self.constant.checked_mul(factor)?,
+        };
+        for (symbol, coefficient) in self.terms() {
+            product
+                .terms
+                .insert(symbol.clone(), coefficient.checked_mul(factor)?);
+        }
+        Some(product)
+    }
+
+    /// Divides the expression by a divisor of all of its coefficients and its constant.
+    pub(crate) fn checked_div(&self, divisor: i128) -> Option<Self> {
+        let mut quotient = Self {
+            terms: BTreeMap::new(),
+            constant: self.constant.checked_div(divisor)?,
+        };
+        for (symbol, coefficient) in &self.terms {
+            quotient
+                .terms
+                .insert(symbol.clone(), coefficient.checked_div(divisor)?);
+        }
+        Some(quotient)
+    }
+
+    /// Replaces the given symbol by an expression.
+    pub(crate) fn substitute(&self, symbol: &Symbol, value: &Self) -> Option<Self> {
+        let coefficient = self.coefficient(symbol);
+        if coefficient == 0 {
+            return Some(self.clone());
+        }
+        let mut rest = self.clone();
+        rest.terms.remove(symbol);
+        rest.checked_add(&value.checked_scale(coefficient)?)
+    }
+
+    pub(crate) fn terms(&self) -> impl Iterator<Item = (&Symbol, i128)> {
+        self.terms
+            .iter()
+            .map(|(symbol, coefficient)| (symbol, *coefficient))
+    }
+
+    pub(crate) fn coefficients(&self) -> impl Iterator<Item = i128> + '_ {
+        self.terms.values().copied()
+    }
+
+    pub(crate) fn evaluate_with<F: Fn(&Symbol) -> i128>(&self, value: F) -> Option<i128> {
+        let mut sum = self.constant;
+        for (symbol, coefficient) in self.terms() {
+            sum = sum.checked_add(coefficient.checked_mul(value(symbol))?)?;
+        }
+        Some(sum)
+    }
+
+    // helper function to print the symbols, leaving out the constant
+    fn fmt_terms(&self, f: &mut Formatter) -> fmt::Result {
+        for (idx, (symbol, coefficient)) in self.terms.iter().enumerate() {
+            let abs = match (idx, *coefficient) {
+                (0, coefficient) if coefficient < 0 => {
+                    write!(f, "-")?;
+                    -coefficient
+                }
+                (0, coefficient) => coefficient,
+                (_, coefficient) if coefficient < 0 => {
+                    write!(f, " - ")?;
+                    -coefficient
+                }
+                (_, coefficient) => {
+                    write!(f, " + ")?;
+                    coefficient
+                }
+            };
+            if abs == 1 {
+                write!(f, "{}", symbol)?;
+            } else {
+                write!(f, "{}*{}", abs, symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<i64> for Linear {
+    fn from(value: i64) -> Self {
+        Self {
+            terms: BTreeMap::new(),
+            constant: i128::from(value),
+        }
+    }
+}
+
+impl From<&Symbol> for Linear {
+    fn from(symbol: &Symbol) -> Self {
+        let mut terms = BTreeMap::new();
+        terms.insert(symbol.clone(), 1);
+        Self { terms, constant: 0 }
+    }
+}
+
+impl From<Symbol> for Linear {
+    fn from(symbol: Symbol) -> Self {
+        Self::from(&symbol)
+    }
+}
+
+impl From<&Linear> for Linear {
+    fn from(expr: &Linear) -> Self {
+        expr.clone()
+    }
+}
+
+impl Display for Linear {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_terms(f)?;
+        match (self.terms.is_empty(), self.constant) {
+            (true, constant) => write!(f, "{}", constant),
+            (false, 0) => Ok(()),
+            (false, constant) if constant < 0 => write!(f, " - {}", -constant),
+            (false, constant) => write!(f, " + {}", constant),
+        }
+    }
+}
+
+/// How a [`Constraint`] compares its expression to zero.
+///
+/// [`Constraint`]: struct.Constraint.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// The expression is zero.
+    Zero,
+    /// The expression is not zero.
+    NonZero,
+    /// The expression is zero or positive.
+    NonNegative,
+}
+
+impl Relation {
+    /// Returns `true` if the relation holds for the given value of the expression.
+    pub fn holds(self, value: i128) -> bool {
+        match self {
+            Relation::Zero => value == 0,
+            Relation::NonZero => value != 0,
+            Relation::NonNegative => value >= 0,
+        }
+    }
+}
+
+/// A linear constraint on [`Symbol`]s, comparing an expression to zero.
+///
+/// The constructors take anything that converts into a [`Linear`] expression
+/// (symbols, constants and expressions), and panic if the difference of
+/// the two sides overflows.
+///
+/// [`Symbol`]: struct.Symbol.html
+/// [`Linear`]: struct.Linear.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constraint {
+    /// The expression that is compared to zero.
+    pub expr: Linear,
+    /// How the expression is compared to zero.
+    pub relation: Relation,
+}
+
+impl Constraint {
+    /// `lhs == rhs`
+    pub fn equal<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::compare(lhs.into(), rhs.into(), Relation::Zero)
+    }
+
+    /// `lhs != rhs`
+    pub fn not_equal<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::compare(lhs.into(), rhs.into(), Relation::NonZero)
+    }
+
+    /// `lhs >= rhs`
+    pub fn greater_or_equal<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::compare(lhs.into(), rhs.into(), Relation::NonNegative)
+    }
+
+    /// `lhs > rhs`, which is `lhs >= rhs + 1` for integers.
+    pub fn greater<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::checked_greater(&lhs.into(), &rhs.into()).expect("overflow")
+    }
+
+    /// `lhs <= rhs`
+    pub fn less_or_equal<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::greater_or_equal(rhs, lhs)
+    }
+
+    /// `lhs < rhs`
+    pub fn less<L: Into<Linear>, R: Into<Linear>>(lhs: L, rhs: R) -> Self {
+        Self::greater(rhs, lhs)
+    }
+
+    fn compare(lhs: Linear, rhs: Linear, relation: Relation) -> Self {
+        Self::checked_compare(&lhs, &rhs, relation).expect("overflow")
+    }
+
+    // helper function to create `lhs > rhs`, or `None` if it overflows
+    pub(crate) fn checked_greater(lhs: &Linear, rhs: &Linear) -> Option<Self> {
+        let rhs = rhs.checked_add(&Linear::from(1))?;
+        Self::checked_compare(lhs, &rhs, Relation::NonNegative)
+    }
+
+    // helper function to compare `lhs - rhs` to zero, or `None` if the difference overflows
+    pub(crate) fn checked_compare(lhs: &Linear, rhs: &Linear, relation: Relation) -> Option<Self> {
+        Some(Self {
+            expr: lhs.checked_sub(rhs)?,
+            relation,
+        })
+    }
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // print `x <= 9` rather than `-x >= -9`
+        let negated = self
+            .expr
+            .coefficients()
+            .next()
+            .is_some_and(|first| first < 0);
+        let relation = match (self.relation, negated) {
+            (Relation::Zero, _) => "==",
+            (Relation::NonZero, _) => "!=",
+            (Relation::NonNegative, false) => ">=",
+            (Relation::NonNegative, true) => "<=",
+        };
+        if self.expr.terms.is_empty() {
+            return write!(f, "{} {} 0", self.expr.constant, relation);
+        }
+        let expr = if negated {
+            self.expr.checked_scale(-1).ok_or(fmt::Error)?
+        } else {
+            self.expr.clone()
+        };
+        // move the constant to the right-hand side
+        expr.fmt_terms(f)?;
+        write!(f, " {} {}", relation, -expr.constant)
+    }
+}
+
+/// The reason a path of a [`SymbolicComputer`] ended.
+///
+/// [`SymbolicComputer`]: struct.SymbolicComputer.html
+#[derive(Debug)]
+pub enum PathEnd<W: Word = i64> {
+    /// The program terminated.
+    Halted,
+    /// The program failed, for all values of the symbols that follow the path.
+    Error(IntcodeError<W>),
+    /// One of the limits was exceeded.
+    LimitExceeded(LimitExceeded),
+    /// The instruction at `pc` can not be executed symbolically.
+    Unsupported { pc: usize, reason: &'static str },
+    /// The path forked after the path limit was reached.
+    Abandoned,
+}
+
+/// A path through a program, found by a [`SymbolicComputer`].
+///
+/// The path is taken for the values of the symbols that satisfy its constraints.
+///
+/// [`SymbolicComputer`]: struct.SymbolicComputer.html
+#[derive(Debug)]
+pub struct Path<W: Word = i64> {
+    /// The reason the path ended.
+    pub end: PathEnd<W>,
+    /// The program counter at the end of the path.
+    pub pc: usize,
+    /// The constraints collected at the comparisons and jumps along the path.
+    pub constraints: Vec<Constraint>,
+    /// The symbols of the inputs the path read, beyond the inputs that were pushed.
+    pub inputs: Vec<Symbol>,
+    /// The values the path outputted.
+    pub outputs: Vec<Linear>,
+    cells: BTreeMap<usize, Linear>,
+    memory: Memory<W>,
+}
+
+impl<W: Word> Path<W> {
+    /// Returns the value at the given location at the end of the path.
+    ///
+    /// Returns `None` if the location is beyond the memory limit, or holds
+    /// a word that does not fit in 64 bits.
+    pub fn memory(&self, address: usize) -> Option<Linear> {
+        match self.cells.get(&address) {
+            Some(value) => Some(value.clone()),
+            None => self.memory.get(address).ok()?.to_i64().map(Linear::from),
+        }
+    }
+}
+
+/// The state of a path that is still being followed.
+#[derive(Clone, Debug)]
+struct State {
+    cells: BTreeMap<usize, Linear>,
+    pc: usize,
+    relative_base: i64,
+    pending_input: VecDeque<Option<Linear>>,
+    inputs: Vec<Symbol>,
+    outputs: Vec<Linear>,
+    constraints: Vec<Constraint>,
+    executed: u64,
+}
+
+/// The reason a path stopped, before the program counter and instruction are known.
+enum Stop<W: Word> {
+    Halted,
+    Abandoned,
+    Fault(Fault<W>),
+    Unsupported(&'static str),
+}
+
+impl<W: Word> Stop<W> {
+    fn at(self, pc: usize, instruction: W) -> PathEnd<W> {
+        match self {
+            Stop::Halted => PathEnd::Halted,
+            Stop::Abandoned => PathEnd::Abandoned,
+            Stop::Fault(Fault::LimitExceeded(limit)) => PathEnd::LimitExceeded(limit),
+            Stop::Fault(fault) => PathEnd::Error(fault.at(pc, instruction)),
+            Stop::Unsupported(reason) => PathEnd::Unsupported { pc, reason },
+        }
+    }
+}
+
+impl<W: Word> From<Fault<W>> for Stop<W> {
+    fn from(fault: Fault<W>) -> Self {
+        Stop::Fault(fault)
+    }
+}
+
+impl<W: Word> From<MemoryLimitExceeded> for Stop<W> {
+    fn from(err: MemoryLimitExceeded) -> Self {
+        Stop::Fault(err.into())
+    }
+}
+
+/// Runs a program on symbolic values, following every path the program can take.
+///
+/// Memory cells and inputs can be [`Symbol`]s, and the computer keeps every value as
+/// a [`Linear`] expression of the symbols. When a comparison or a jump depends on the
+/// symbols, the computer follows both outcomes, adding the matching [`Constraint`] to
+/// each path. Paths whose constraints can not be satisfied are dropped.
+///
+/// The values are unbounded integers, so overflowing the word type is not detected.
+/// Multiplying two symbolic values, writing to a symbolic location, and jumping to or executing
+/// a symbolic value end the path as [`PathEnd::Unsupported`]. Reading from a symbolic location
+/// gives an *approximate* symbol (see [`Symbol::is_approximate`]).
+///
+/// Each path executes at most [`DEFAULT_PATH_INSTRUCTIONS`] instructions, unless other
+/// limits are set, and at most [`DEFAULT_PATH_LIMIT`] paths are explored.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// // memory[0] = memory[9] * 3 + memory[10]
+/// let memory = vec![1002, 9, 3, 0, 1, 0, 10, 0, 99, 0, 0];
+/// let mut computer = SymbolicComputer::new(&memory);
+/// let x = computer.symbolize(9, "x");
+/// let y = computer.symbolize(10, "y");
+/// computer.assume(Constraint::greater_or_equal(&y, 0));
+/// computer.assume(Constraint::less(&y, 3));
+///
+/// let (path, solution) = computer
+///     .find(|path| Some(vec![Constraint::equal(path.memory(0)?, 3_000_000_001)]))
+///     .unwrap();
+///
+/// assert_eq!(path.memory(0).unwrap().to_string(), "3*x + y");
+/// assert_eq!(solution.value(&x), Some(1_000_000_000));
+/// assert_eq!(solution.value(&y), Some(1));
+/// ```
+///
+/// [`Symbol`]: struct.Symbol.html
+/// [`Linear`]: struct.Linear.html
+/// [`Constraint`]: struct.Constraint.html
+/// [`PathEnd::Unsupported`]: enum.PathEnd.html#variant.Unsupported
+/// [`Symbol::is_approximate`]: struct.Symbol.html#method.is_approximate
+/// [`DEFAULT_PATH_INSTRUCTIONS`]: constant.DEFAULT_PATH_INSTRUCTIONS.html
+/// [`DEFAULT_PATH_LIMIT`]: constant.DEFAULT_PATH_LIMIT.html
+#[derive(Clone, Debug)]
+pub struct SymbolicComputer<W: Word = i64> {
+    memory: Memory<W>,
+    cells: BTreeMap<usize, Linear>,
+    pc: usize,
+    relative_base: i64,
+    pending_input: VecDeque<Option<Linear>>,
+    assumptions: Vec<Constraint>,
+    symbols: usize,
+    limits: Limits,
+    path_limit: usize,
+}
+
+impl<W: Word> SymbolicComputer<W> {
+    /// Creates a symbolic computer for the given memory image, without any symbols.
+    pub fn new(memory: &[W]) -> Self {
+        Self::from_parts(Memory::new(memory.to_vec()), 0, 0, VecDeque::new())
+    }
+
+    pub(crate) fn from_parts(
+        memory: Memory<W>,
+        pc: usize,
+        relative_base: i64,
+        pending_input: VecDeque<W>,
+    ) -> Self {
+        let mut computer = Self {
+            memory,
+            cells: BTreeMap::new(),
+            pc,
+            relative_base,
+            pending_input: VecDeque::new(),
+            assumptions: Vec::new(),
+            symbols: 0,
+            limits: Limits::default(),
+            path_limit: DEFAULT_PATH_LIMIT,
+        };
+        computer.set_limits(Limits {
+            instructions: Some(DEFAULT_PATH_INSTRUCTIONS),
+            memory: computer.memory.limit(),
+            time: None,
+        });
+        computer.pending_input = pending_input
+            .iter()
+            .map(|word| word.to_i64().map(Linear::from))
+            .collect();
+        computer
+    }
+
+    /// Creates a new symbol with the given name.
+    pub fn symbol(&mut self, name: &str) -> Symbol {
+        self.symbols += 1;
+        Symbol {
+            id: self.symbols - 1,
+            name: Arc::from(name),
+            approximate: false,
+        }
+    }
+
+    /// Sets the memory cell at the given location to a symbolic value.
+    pub fn set<V: Into<Linear>>(&mut self, address: usize, value: V) {
+        self.cells.insert(address, value.into());
+    }
+
+    /// Replaces the memory cell at the given location by a new symbol, which is returned.
+    pub fn symbolize(&mut self, address: usize, name: &str) -> Symbol {
+        let symbol = self.symbol(name);
+        self.set(address, &symbol);
+        symbol
+    }
+
+    /// Pushes a value to the input queue.
+    ///
+    /// Once the queue is empty, each input reads a new symbol, named `input0`, `input1` etc.
+    pub fn push_input<V: Into<Linear>>(&mut self, value: V) {
+        self.pending_input.push_back(Some(value.into()));
+    }
+
+    /// Adds a constraint that holds for all paths, e.g. a range of one of the symbols.
+    pub fn assume(&mut self, constraint: Constraint) {
+        self.assumptions.push(constraint);
+    }
+
+    /// Sets the limits of each path.
+    ///
+    /// The instruction limit applies to each path, the time limit to the whole exploration.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+        self.memory.set_limit(limits.memory);
+    }
+
+    /// Returns the limits of each path.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Sets the maximal number of paths to explore.
+    pub fn set_path_limit(&mut self, paths: usize) {
+        self.path_limit = paths;
+    }
+
+    /// Follows all paths of the program, until each of them ends.
+    ///
+    /// Once the path limit is reached, the paths end as [`PathEnd::Abandoned`] when they fork.
+    ///
+    /// [`PathEnd::Abandoned`]: enum.PathEnd.html#variant.Abandoned
+    pub fn explore(&self) -> Vec<Path<W>> {
+        let mut explorer = Explorer {
+            computer: self,
+            symbols: self.symbols,
+            paths: 1,
+            started: Instant::now(),
+        };
+        let mut pending = vec![State {
+            cells: self.cells.clone(),
+            pc: self.pc,
+            relative_base: self.relative_base,
+            pending_input: self.pending_input.clone(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            constraints: self.assumptions.clone(),
+            executed: 0,
+        }];
+        let mut paths = Vec::new();
+        while let Some(mut state) = pending.pop() {
+            let end = loop {
+                if let Err(end) = explorer.step(&mut state, &mut pending) {
+                    break end;
+                }
+            };
+            paths.push(Path {
+                end,
+                pc: state.pc,
+                constraints: state.constraints,
+                inputs: state.inputs,
+                outputs: state.outputs,
+                cells: state.cells,
+                memory: self.memory.clone(),
+            });
+        }
+        paths
+    }
+
+    /// Finds a path that halts, and values of the symbols for which the path also
+    /// satisfies the constraints returned by `goal`.
+    ///
+    /// `goal` returns `None` for paths that can not satisfy it.
+    /// See [`SymbolicComputer`] for an example.
+    ///
+    /// [`SymbolicComputer`]: struct.SymbolicComputer.html
+    pub fn find<F>(&self, goal: F) -> Option<(Path<W>, Solution)>
+    where
+        F: Fn(&Path<W>) -> Option<Vec<Constraint>>,
+    {
+        for path in self.explore() {
+            if let PathEnd::Halted = path.end {
+                let mut constraints = match goal(&path) {
+                    Some(constraints) => constraints,
+                    None => continue,
+                };
+                constraints.extend(path.constraints.iter().cloned());
+                if let SolverResult::Satisfiable(solution) = solve(&constraints) {
+                    return Some((path, solution));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Follows the paths of a `SymbolicComputer`.
+struct Explorer<'a, W: Word> {
+    computer: &'a SymbolicComputer<W>,
+    symbols: usize,
+    paths: usize,
+    started: Instant,
+}
+
+impl<'a, W: Word> Explorer<'a, W> {
+    /// Executes a single instruction of the path, pushing the other outcome of a
+    /// comparison or jump to `pending`.
+    fn step(&mut self, state: &mut State, pending: &mut Vec<State>) -> Result<(), PathEnd<W>> {
+        let pc = state.pc;
+        let limits = self.computer.limits;
+        if let Some(limit) = limits.instructions {
+            if state.executed >= limit {
+                return Err(PathEnd::LimitExceeded(LimitExceeded::Instructions {
+                    limit,
+                }));
+            }
+        }
+        if let Some(limit) = limits.time {
+            if state.executed.is_multiple_of(TIME_CHECK_INTERVAL) && self.started.elapsed() >= limit
+            {
+                return Err(PathEnd::LimitExceeded(LimitExceeded::Time { limit }));
+            }
+        }
+
+        let instruction = self
+            .cell(state, pc)
+            .map_err(|stop| stop.at(pc, W::from_i64(0)))?;
+        let instruction = instruction
+            .as_constant()
+            .and_then(|instruction| i64::try_from(instruction).ok())
+            .ok_or(PathEnd::Unsupported {
+                pc,
+                reason: "the instruction is symbolic",
+            })?;
+        // the values of the cells are checked to fit in a word
+        self.execute(state, instruction, pending)
+            .map_err(|stop| stop.at(pc, W::from_i64(instruction)))?;
+        state.executed += 1;
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        state: &mut State,
+        instruction: i64,
+        pending: &mut Vec<State>,
+    ) -> Result<(), Stop<W>> {
+        let pc = state.pc;
+        let opcode = Opcode::from_i64(instruction).map_err(Fault::from)?;
+        let mut params = Vec::new();
+        for idx in 1..opcode.instruction_size() {
+            params.push(self.cell(state, pc + idx)?);
+        }
+        let next = pc + opcode.instruction_size();
+
+        match opcode {
+            Opcode::Add(src1_mode, src2_mode, dst_mode)
+            | Opcode::Multiply(src1_mode, src2_mode, dst_mode) => {
+                // get the parameters
+                let src1 = self.value(state, src1_mode, &params[0])?;
+                let src2 = self.value(state, src2_mode, &params[1])?;
+                let dst = self.address(state, dst_mode, &params[2])?;
+
+                // perform the operation
+                let value = match opcode {
+                    Opcode::Add(_, _, _) => src1.checked_add(&src2),
+                    _ => match (src1.as_constant(), src2.as_constant()) {
+                        (Some(factor), _) => src2.checked_scale(factor),
+                        (None, Some(factor)) => src1.checked_scale(factor),
+                        (None, None) => return Err(Stop::Unsupported("non-linear multiplication")),
+                    },
+                };
+                state.cells.insert(dst, word(value)?);
+            }
+            Opcode::Input(dst_mode) => {
+                // get the parameters
+                let dst = self.address(state, dst_mode, &params[0])?;
+                let input = match state.pending_input.pop_front() {
+                    Some(Some(input)) => input,
+                    Some(None) => {
+                        return Err(Stop::Unsupported("an input does not fit in 64 bits"))
+                    }
+                    None => {
+                        let symbol = self.symbol(format!("input{}", state.inputs.len()), false);
+                        state.inputs.push(symbol.clone());
+                        Linear::from(symbol)
+                    }
+                };
+
+                // perform the operation
+                state.cells.insert(dst, input);
+            }
+            Opcode::Output(src_mode) => {
+                let src = self.value(state, src_mode, &params[0])?;
+                state.outputs.push(src);
+            }
+            Opcode::JumpIfTrue(cond_mode, loc_mode) | Opcode::JumpIfFalse(cond_mode, loc_mode) => {
+                // get the parameters
+                let cond = self.value(state, cond_mode, &params[0])?;
+                let loc = self.value(state, loc_mode, &params[1]);
+
+                // perform the operation
+                let (jump, no_jump) = match opcode {
+                    Opcode::JumpIfTrue(_, _) => (Relation::NonZero, Relation::Zero),
+                    _ => (Relation::Zero, Relation::NonZero),
+                };
+                let jumps = match cond.as_constant() {
+                    Some(cond) => jump.holds(cond),
+                    None => {
+                        let mut other = state.clone();
+                        other.constraints.push(Constraint {
+                            expr: cond.clone(),
+                            relation: no_jump,
+                        });
+                        other.pc = next;
+                        state.constraints.push(Constraint {
+                            expr: cond,
+                            relation: jump,
+                        });
+                        self.fork(state, other, pending)?
+                    }
+                };
+                if jumps {
+                    let loc = loc?;
+                    state.pc = match loc.as_constant() {
+                        Some(loc) => location(loc)?,
+                        None => return Err(Stop::Unsupported("jump to a symbolic location")),
+                    };
+                } else {
+                    state.pc = next;
+                }
+                return Ok(());
+            }
+            Opcode::LessThan(operand1_mode, operand2_mode, dst_mode)
+            | Opcode::Equals(operand1_mode, operand2_mode, dst_mode) => {
+                // get the parameters
+                let operand1 = self.value(state, operand1_mode, &params[0])?;
+                let operand2 = self.value(state, operand2_mode, &params[1])?;
+                let dst = self.address(state, dst_mode, &params[2])?;
+
+                // perform the operation
+                let (holds, fails) = match opcode {
+                    Opcode::LessThan(_, _, _) => (
+                        Constraint::checked_greater(&operand2, &operand1),
+                        Constraint::checked_compare(&operand1, &operand2, Relation::NonNegative),
+                    ),
+                    _ => (
+                        Constraint::checked_compare(&operand1, &operand2, Relation::Zero),
+                        Constraint::checked_compare(&operand1, &operand2, Relation::NonZero),
+                    ),
+                };
+                let (holds, fails) = (checked(holds)?, checked(fails)?);
+                match holds.expr.as_constant() {
+                    Some(value) => {
+                        let result = i64::from(holds.relation.holds(value));
+                        state.cells.insert(dst, Linear::from(result));
+                    }
+                    None => {
+                        let mut other = state.clone();
+                        other.constraints.push(fails);
+                        other.cells.insert(dst, Linear::from(0));
+                        other.pc = next;
+                        state.constraints.push(holds);
+                        state.cells.insert(dst, Linear::from(1));
+                        self.fork(state, other, pending)?;
+                    }
+                }
+            }
+            Opcode::AdjustRelativeBase(offset_mode) => {
+                let offset = self.value(state, offset_mode, &params[0])?;
+                let offset = match offset.as_constant() {
+                    Some(offset) => offset,
+                    None => return Err(Stop::Unsupported("symbolic relative base")),
+                };
+                state.relative_base = i64::try_from(i128::from(state.relative_base) + offset)
+                    .map_err(|_| Fault::<W>::Overflow)?;
+            }
+            Opcode::Terminate => return Err(Stop::Halted),
+        }
+        state.pc = next;
+        Ok(())
+    }
+
+    /// Continues with `state` if it is feasible (pushing `other` if it is feasible as well),
+    /// and with `other` otherwise. Returns `true` if the path continues with `state`.
+    fn fork(
+        &mut self,
+        state: &mut State,
+        other: State,
+        pending: &mut Vec<State>,
+    ) -> Result<bool, Stop<W>> {
+        let feasible = |state: &State| {
+            solve_with_limit(&state.constraints, FEASIBILITY_LIMIT) != SolverResult::Unsatisfiable
+        };
+        match (feasible(state), feasible(&other)) {
+            (true, true) if self.paths >= self.computer.path_limit => Err(Stop::Abandoned),
+            (true, true) => {
+                self.paths += 1;
+                pending.push(other);
+                Ok(true)
+            }
+            (false, true) => {
+                *state = other;
+                Ok(false)
+            }
+            _ => Ok(true),
+        }
+    }
+
+    // helper function to create a new symbol
+    fn symbol(&mut self, name: String, approximate: bool) -> Symbol {
+        self.symbols += 1;
+        Symbol {
+            id: self.symbols - 1,
+            name: Arc::from(name),
+            approximate,
+        }
+    }
+
+    // helper function to read a memory location
+    fn cell(&self, state: &State, address: usize) -> Result<Linear, Stop<W>> {
+        if let Some(value) = state.cells.get(&address) {
+            return Ok(value.clone());
+        }
+        match self.computer.memory.get(address)?.to_i64() {
+            Some(value) => Ok(Linear::from(value)),
+            None => Err(Stop::Unsupported("a word does not fit in 64 bits")),
+        }
+    }
+
+    // helper function to get the (possibly symbolic) location a parameter points to
+    fn pointer(
+        &self,
+        state: &State,
+        mode: ParameterMode,
+        param: &Linear,
+    ) -> Result<Linear, Stop<W>> {
+        match mode {
+            ParameterMode::Position => Ok(param.clone()),
+            ParameterMode::Relative => word(param.checked_add(&Linear::from(state.relative_base))),
+            ParameterMode::Immediate => Err(Fault::ImmediateWrite.into()),
+        }
+    }
+
+    // helper function to get the memory location a parameter points to
+    fn address(
+        &self,
+        state: &State,
+        mode: ParameterMode,
+        param: &Linear,
+    ) -> Result<usize, Stop<W>> {
+        let address = match self.pointer(state, mode, param)?.as_constant() {
+            Some(address) => location(address)?,
+            None => return Err(Stop::Unsupported("write to a symbolic location")),
+        };
+        match self.computer.memory.limit() {
+            Some(limit) if address >= limit => Err(MemoryLimitExceeded { address, limit }.into()),
+            _ => Ok(address),
+        }
+    }
+
+    // helper function to get the value of a parameter
+    fn value(
+        &mut self,
+        state: &mut State,
+        mode: ParameterMode,
+        param: &Linear,
+    ) -> Result<Linear, Stop<W>> {
+        if let ParameterMode::Immediate = mode {
+            return Ok(param.clone());
+        }
+        let pointer = self.pointer(state, mode, param)?;
+        if let Some(address) = pointer.as_constant() {
+            return self.cell(state, location(address)?);
+        }
+
+        // the location is only known symbolically, so the value could be anything
+        let zero = Linear::from(0);
+        let in_memory = Constraint::checked_compare(&pointer, &zero, Relation::NonNegative);
+        state.constraints.push(checked(in_memory)?);
+        if let Some(limit) = self.computer.memory.limit() {
+            let limit = Linear::from(i64::try_from(limit).unwrap_or(i64::MAX));
+            let below_limit = Constraint::checked_greater(&limit, &pointer);
+            state.constraints.push(checked(below_limit)?);
+        }
+        Ok(Linear::from(self.symbol(format!("[{}]", pointer), true)))
+    }
+}
+
+// helper function to end the path when a constraint overflowed
+fn checked<W: Word>(constraint: Option<Constraint>) -> Result<Constraint, Stop<W>> {
+    constraint.ok_or(Stop::Unsupported("the coefficients overflowed"))
+}
+
+// helper function to check that a value fits in a word
+fn word<W: Word>(value: Option<Linear>) -> Result<Linear, Stop<W>> {
+    let value = value.ok_or(Stop::Unsupported("the coefficients overflowed"))?;
+    match value.as_constant() {
+        Some(constant)
+            if i64::try_from(constant)
+                .ok()
+                .and_then(W::checked_from_i64)
+                .is_none() =>
+        {
+            Err(Fault::Overflow.into())
+        }
+        _ => Ok(value),
+    }
+}
+
+// helper function to turn a constant into a memory location
+fn location<W: Word>(address: i128) -> Result<usize, Stop<W>> {
+    let word = i64::try_from(address).map_err(|_| Fault::<W>::Overflow)?;
+    usize::try_from(word).map_err(|_| match W::checked_from_i64(word) {
+        Some(word) => Fault::InvalidAddress(word).into(),
+        None => Fault::Overflow.into(),
+    })
+}
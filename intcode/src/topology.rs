@@ -0,0 +1,213 @@
+use crate::{Network, Word};
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// An error which can occour while parsing a [`Topology`].
+///
+/// The error points to the line (starting at 1) that caused it.
+///
+/// [`Topology`]: struct.Topology.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopologyError {
+    /// The line at which the error occurred.
+    pub line: usize,
+    msg: String,
+}
+
+impl TopologyError {
+    fn new(line: usize, msg: impl Into<String>) -> Self {
+        Self {
+            line,
+            msg: msg.into(),
+        }
+    }
+}
+
+impl Display for TopologyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.line, self.msg)
+    }
+}
+
+impl error::Error for TopologyError {}
+
+/// A description of how machines that run the same program are wired into a [`Network`].
+///
+/// # Syntax
+/// Each line holds a single statement, and everything after a `#` is a comment.
+/// A machine must be declared before it is used by the other statements.
+///
+/// The statements are:
+/// - `machine NAME` (or `machine NAME = v1, v2, ...`) - declares a machine, with optional initial inputs.
+/// - `input NAME = v1, v2, ...` - queues more inputs for the machine.
+/// - `link FROM -> TO1, TO2, ...` - routes the outputs of a machine to other machines (fan-out).
+///   Several machines may be linked to the same one (fan-in).
+/// - `result NAME` - the machine whose last output is the result of the network (exactly once).
+///
+/// The inputs of each machine are queued in the order they appear.
+///
+/// # Example
+/// ```
+/// use intcode::*;
+///
+/// // machines that add 1 to their input, the first one feeding the others
+/// let source = "
+///     machine a = 1
+///     machine b
+///     machine c = 5
+///     link a -> b, c
+///     result c
+/// ";
+/// let topology: Topology = source.parse().unwrap();
+/// assert_eq!(topology.machine("b"), Some(1));
+///
+/// let mut network = topology.network(&[3, 9, 1001, 9, 1, 9, 4, 9, 99, 0]);
+/// assert!(matches!(network.run(), NetworkHalt::Halted));
+/// assert_eq!(network.outputs(topology.result()), &[6]);
+/// ```
+///
+/// [`Network`]: struct.Network.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Topology<W: Word = i64> {
+    machines: Vec<String>,
+    inputs: Vec<(usize, W)>,
+    links: Vec<(usize, usize)>,
+    result: usize,
+}
+
+impl<W: Word> Topology<W> {
+    /// Returns the names of the machines, in the order they were declared.
+    pub fn machines(&self) -> impl Iterator<Item = &str> {
+        self.machines.iter().map(String::as_str)
+    }
+
+    /// Returns the index of the machine with the given name, which is also its index in the network.
+    pub fn machine(&self, name: &str) -> Option<usize> {
+        self.machines.iter().position(|machine| machine == name)
+    }
+
+    /// Returns the index of the machine whose last output is the result.
+    pub fn result(&self) -> usize {
+        self.result
+    }
+
+    /// Creates a [`Network`] of machines that run the given program, wired as described.
+    ///
+    /// [`Network`]: struct.Network.html
+    pub fn network(&self, memory: &[W]) -> Network<W> {
+        let mut network = Network::new();
+        for _ in &self.machines {
+            network.add_machine(memory.to_vec());
+        }
+        for (machine, value) in &self.inputs {
+            network.push_input(*machine, value.clone());
+        }
+        for (from, to) in &self.links {
+            network.connect(*from, *to);
+        }
+        network
+    }
+}
+
+impl<W: Word> FromStr for Topology<W> {
+    type Err = TopologyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut machines: Vec<String> = Vec::new();
+        let mut inputs = Vec::new();
+        let mut links = Vec::new();
+        let mut result = None;
+
+        for (idx, line) in source.lines().enumerate() {
+            let line_number = idx + 1;
+            let error = |msg: String| TopologyError::new(line_number, msg);
+            let text = line.split('#').next().unwrap_or("").trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let (keyword, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+            let rest = rest.trim();
+            // helper closure to find a declared machine
+            let machine = |name: &str| {
+                let name = name.trim();
+                machines
+                    .iter()
+                    .position(|machine| machine == name)
+                    .ok_or_else(|| error(format!("unknown machine `{}`", name)))
+            };
+            // helper closure to parse comma-separated values
+            let values = |values: &str| {
+                values
+                    .split(',')
+                    .map(|value| {
+                        let value = value.trim();
+                        value
+                            .parse::<W>()
+                            .map_err(|_| error(format!("invalid value `{}`", value)))
+                    })
+                    .collect::<Result<Vec<W>, TopologyError>>()
+            };
+
+            match keyword {
+                "machine" => {
+                    let (name, initial) = match rest.split_once('=') {
+                        Some((name, initial)) => (name.trim(), values(initial)?),
+                        None => (rest, Vec::new()),
+                    };
+                    if !is_valid_name(name) {
+                        return Err(error(format!("invalid machine name `{}`", name)));
+                    }
+                    if machines.iter().any(|machine| machine == name) {
+                        return Err(error(format!("machine `{}` is already declared", name)));
+                    }
+                    machines.push(name.to_string());
+                    let id = machines.len() - 1;
+                    inputs.extend(initial.into_iter().map(|value| (id, value)));
+                }
+                "input" => {
+                    let (name, queued) = rest
+                        .split_once('=')
+                        .ok_or_else(|| error(String::from("expected `input NAME = values`")))?;
+                    let id = machine(name)?;
+                    inputs.extend(values(queued)?.into_iter().map(|value| (id, value)));
+                }
+                "link" => {
+                    let (from, to) = rest
+                        .split_once("->")
+                        .ok_or_else(|| error(String::from("expected `link FROM -> TO`")))?;
+                    let from = machine(from)?;
+                    for to in to.split(',') {
+                        links.push((from, machine(to)?));
+                    }
+                }
+                "result" => {
+                    if result.is_some() {
+                        return Err(error(String::from("the result is already set")));
+                    }
+                    result = Some(machine(rest)?);
+                }
+                _ => return Err(error(format!("unknown statement `{}`", keyword))),
+            }
+        }
+
+        let last_line = source.lines().count();
+        Ok(Self {
+            result: result.ok_or_else(|| TopologyError::new(last_line, "missing `result`"))?,
+            machines,
+            inputs,
+            links,
+        })
+    }
+}
+
+// helper function to check that a machine name is made of letters, digits, `_` and `-`
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
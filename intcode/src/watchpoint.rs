@@ -0,0 +1,69 @@
+use crate::Word;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// The kind of memory access that triggers a watchpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchKind<W: Word> {
+    /// Triggers when a parameter in *position* or *relative* mode reads the location.
+    Read,
+    /// Triggers when an instruction writes the location.
+    Write,
+    /// Triggers when an instruction writes the given value to the location.
+    WriteValue(W),
+}
+
+/// A report of a triggered watchpoint, returned by the [`IntcodeComputer`]
+/// as [`HaltReason::Watchpoint`].
+///
+/// For reads, `old` and `new` are both the value that was read.
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+/// [`HaltReason::Watchpoint`]: enum.HaltReason.html#variant.Watchpoint
+#[derive(Clone, Debug, PartialEq)]
+pub struct Watchpoint<W: Word> {
+    /// The location that was accessed.
+    pub addr: usize,
+    /// The value of the location before the access.
+    pub old: W,
+    /// The value of the location after the access.
+    pub new: W,
+    /// The location of the instruction that accessed the memory.
+    pub pc: usize,
+}
+
+impl<W: Word> Display for Watchpoint<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Watchpoint on location {}: {} -> {} (pc: {})",
+            self.addr, self.old, self.new, self.pc
+        )
+    }
+}
+
+/// A watchpoint set on a range of memory locations.
+#[derive(Clone, Debug)]
+pub(crate) struct Watch<W: Word> {
+    pub(crate) addresses: Range<usize>,
+    pub(crate) kind: WatchKind<W>,
+}
+
+impl<W: Word> Watch<W> {
+    /// Returns whether a read of the given location triggers the watchpoint.
+    pub(crate) fn on_read(&self, address: usize) -> bool {
+        self.kind == WatchKind::Read && self.addresses.contains(&address)
+    }
+
+    /// Returns whether writing `value` to the given location triggers the watchpoint.
+    pub(crate) fn on_write(&self, address: usize, value: &W) -> bool {
+        self.addresses.contains(&address)
+            && match &self.kind {
+                WatchKind::Read => false,
+                WatchKind::Write => true,
+                WatchKind::WriteValue(expected) => expected == value,
+            }
+    }
+}
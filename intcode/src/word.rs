@@ -0,0 +1,89 @@
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// A single memory cell of the [`IntcodeComputer`].
+///
+/// The computer is generic over its word type, so that programs that
+/// produce large values can be run with a wider word (`i64`, or `BigInt`
+/// with the `bigint` feature) instead of silently overflowing.
+///
+/// [`IntcodeComputer`]: struct.IntcodeComputer.html
+pub trait Word: Clone + Debug + Display + PartialEq + PartialOrd + FromStr {
+    /// Converts an `i64` into a word, if it fits.
+    fn checked_from_i64(value: i64) -> Option<Self>;
+
+    /// Converts an `i64` that is known to fit (e.g. `0` or `1`) into a word.
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in the word.
+    fn from_i64(value: i64) -> Self {
+        Self::checked_from_i64(value).expect("The value does not fit in a word")
+    }
+
+    /// Converts the word into an `i64`, if it fits.
+    fn to_i64(&self) -> Option<i64>;
+
+    /// Adds two words, returning `None` on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Multiplies two words, returning `None` on overflow.
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// Converts the word into a memory location, if it is a valid one.
+    fn to_address(&self) -> Option<usize> {
+        self.to_i64().and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// Returns `true` if the word is zero.
+    fn is_zero(&self) -> bool {
+        *self == Self::from_i64(0)
+    }
+}
+
+macro_rules! impl_word_for_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl Word for $ty {
+                fn checked_from_i64(value: i64) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+
+                fn to_i64(&self) -> Option<i64> {
+                    i64::try_from(*self).ok()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$ty>::checked_add(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$ty>::checked_mul(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_word_for_primitive!(i32, i64, i128);
+
+#[cfg(feature = "bigint")]
+impl Word for num_bigint::BigInt {
+    fn checked_from_i64(value: i64) -> Option<Self> {
+        Some(value.into())
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
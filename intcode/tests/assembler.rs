@@ -0,0 +1,66 @@
+//! Tests of the assembler's errors: each one must point to the line and column that caused it.
+
+use intcode::*;
+
+// helper function to assemble a faulty source, returning its error
+fn assemble_error(source: &str) -> AssemblyError {
+    assemble::<i64>(source).unwrap_err()
+}
+
+#[test]
+fn undefined_label() {
+    let error = assemble_error("start: in [x]\n       jt #1, #loop\n       hlt\nx:     data 0");
+    assert_eq!((error.line, error.column), (2, 16));
+    assert_eq!(error.to_string(), "2:16: 'loop' is not defined");
+}
+
+#[test]
+fn duplicate_label() {
+    let error = assemble_error("x:  in [x]\n    out [x]\nx:  hlt");
+    assert_eq!((error.line, error.column), (3, 1));
+    assert_eq!(error.to_string(), "3:1: 'x' is already defined");
+
+    let error = assemble_error("const SIZE = 1\n    hlt\nconst SIZE = 2");
+    assert_eq!((error.line, error.column), (3, 7));
+    assert_eq!(error.to_string(), "3:7: 'SIZE' is already defined");
+}
+
+#[test]
+fn constant_cycle() {
+    let error = assemble_error("const A = B + 1\nconst B = A\n    data A");
+    assert_eq!((error.line, error.column), (2, 11));
+    assert_eq!(error.to_string(), "2:11: 'A' is defined in terms of itself");
+
+    // the constants are evaluated when they are used
+    let error = assemble_error("const A = A\n    data A");
+    assert_eq!((error.line, error.column), (1, 11));
+    assert_eq!(error.to_string(), "1:11: 'A' is defined in terms of itself");
+}
+
+#[test]
+fn reserved_name() {
+    for name in &["rb", "data", "const", "add", "hlt"] {
+        let error = assemble_error(&format!("    hlt\n{}: data 0", name));
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.to_string(),
+            format!("2:1: '{}' is a reserved word", name)
+        );
+    }
+
+    let error = assemble_error("    hlt\nconst rb = 1");
+    assert_eq!((error.line, error.column), (2, 7));
+}
+
+#[test]
+fn immediate_write() {
+    let error = assemble_error("    in [0]\n    add [0], #1, #0\n    hlt");
+    assert_eq!((error.line, error.column), (2, 18));
+    assert_eq!(
+        error.to_string(),
+        "2:18: Cannot write to an operand in immediate mode"
+    );
+
+    let error = assemble_error("    in #5");
+    assert_eq!((error.line, error.column), (1, 8));
+}
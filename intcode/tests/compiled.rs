@@ -0,0 +1,351 @@
+//! Differential tests of the compiled backend: every program must give
+//! the same results with `run_compiled` as with the plain interpreter.
+
+use intcode::*;
+use std::{collections::VecDeque, mem};
+
+const DAY_2: &str = include_str!("../../day-2/src/input.txt");
+const DAY_5: &str = include_str!("../../day-5/src/input.txt");
+const DAY_7: &str = include_str!("../../day-7/src/input.txt");
+
+fn parse(program: &str) -> Vec<i64> {
+    program
+        .trim()
+        .split(',')
+        .map(|word| word.parse().unwrap())
+        .collect()
+}
+
+/// The observable results of a run: the return value (or error) and the outputs.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    result: String,
+    outputs: Vec<i64>,
+    executed: u64,
+}
+
+fn computer(memory: &[i64], inputs: &[i64]) -> IntcodeComputer<VecDeque<i64>, Vec<i64>> {
+    IntcodeComputer::new(
+        memory.to_vec(),
+        inputs.iter().copied().collect(),
+        Vec::new(),
+    )
+}
+
+fn outcome(
+    computer: &mut IntcodeComputer<VecDeque<i64>, Vec<i64>>,
+    result: Result<RunReport<i64>, IntcodeError<i64>>,
+) -> Outcome {
+    let outputs = mem::take(computer.output_mut());
+    if let Ok(report) = &result {
+        assert_eq!(report.outputs, outputs);
+    }
+    Outcome {
+        result: format!("{:?}", result.map(|report| report.result)),
+        outputs,
+        executed: computer.instructions_executed(),
+    }
+}
+
+/// Runs the program with both backends, and checks that the outcomes are the same.
+fn check(memory: &[i64], inputs: &[i64], patch: impl Fn(&mut Memory<i64>)) -> Outcome {
+    let program = compile(memory);
+
+    let mut interpreted = computer(memory, inputs);
+    patch(interpreted.memory_mut());
+    let result = interpreted.run();
+    let expected = outcome(&mut interpreted, result);
+
+    let mut compiled = computer(memory, inputs);
+    patch(compiled.memory_mut());
+    let result = compiled.run_compiled(&program);
+    let actual = outcome(&mut compiled, result);
+
+    assert_eq!(actual, expected, "program: {:?}", memory);
+    actual
+}
+
+/// Runs the program with both backends under the given instruction limit, and checks that the outcomes are the same.
+fn check_limited(memory: &[i64], inputs: &[i64], limit: u64) -> Outcome {
+    let program = compile(memory);
+    let limits = Limits {
+        instructions: Some(limit),
+        ..Limits::default()
+    };
+
+    let mut interpreted = computer(memory, inputs);
+    interpreted.set_limits(limits);
+    let result = interpreted.run();
+    let expected = outcome(&mut interpreted, result);
+
+    let mut compiled = computer(memory, inputs);
+    compiled.set_limits(limits);
+    let result = compiled.run_compiled(&program);
+    let actual = outcome(&mut compiled, result);
+
+    assert_eq!(actual, expected, "program: {:?}, limit: {}", memory, limit);
+    actual
+}
+
+fn no_patch(_: &mut Memory<i64>) {}
+
+#[test]
+fn day_2() {
+    let memory = parse(DAY_2);
+    for (noun, verb) in &[(12, 2), (89, 76), (0, 0), (99, 99), (50, 7)] {
+        check(&memory, &[], |memory| {
+            memory.set(1, *noun).unwrap();
+            memory.set(2, *verb).unwrap();
+        });
+    }
+}
+
+#[test]
+fn patched_operands_stay_compiled() {
+    let memory = parse(DAY_2);
+    let program = compile(&memory);
+    // the noun and the verb are operands, so setting them keeps the compiled code
+    assert!(program.is_code(0));
+    assert!(!program.is_code(1) && !program.is_code(2));
+
+    let mut computer = computer(&memory, &[]);
+    computer.poke(1, 12).unwrap();
+    computer.poke(2, 2).unwrap();
+    assert_eq!(computer.run_compiled(&program).unwrap().result, 3058646);
+}
+
+#[test]
+fn watchpoints() {
+    let memory = parse(DAY_2);
+    let program = compile(&memory);
+
+    let mut interpreted = computer(&memory, &[]);
+    interpreted.watch(0..4, WatchKind::Write);
+    let expected = interpreted.run().unwrap().watchpoints;
+    assert!(!expected.is_empty());
+
+    // the watchpoints make the compiled backend fall back to the interpreter
+    let mut compiled = computer(&memory, &[]);
+    compiled.watch(0..4, WatchKind::Write);
+    assert_eq!(
+        compiled.run_compiled(&program).unwrap().watchpoints,
+        expected
+    );
+}
+
+#[test]
+fn day_5() {
+    let memory = parse(DAY_5);
+    for input in 0..10 {
+        check(&memory, &[input], no_patch);
+    }
+    // the input stream ends before the program asks for input
+    check(&memory, &[], no_patch);
+}
+
+#[test]
+fn day_7() {
+    let memory = parse(DAY_7);
+    for phase in 0..10 {
+        for signal in &[0, 1, 17, 12345] {
+            check(&memory, &[phase, *signal], no_patch);
+        }
+    }
+}
+
+#[test]
+fn relative_base_and_large_memory() {
+    // outputs a copy of itself
+    let quine = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let outcome = check(&quine, &[], no_patch);
+    assert_eq!(outcome.outputs, quine);
+
+    check(&[104, 1125899906842624, 99], &[], no_patch);
+    check(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[], no_patch);
+    check(
+        &[1101, 3, 4, 1_000_000_000, 4, 1_000_000_000, 99],
+        &[],
+        no_patch,
+    );
+}
+
+#[test]
+fn self_modification() {
+    // overwrites the output instruction at 0 with a terminate instruction
+    check(&[104, 7, 1101, 0, 99, 0, 1105, 1, 0], &[], no_patch);
+    // patches the immediate operand of the next instruction
+    check(&[1101, 0, 42, 6, 104, 0, 0, 99], &[], no_patch);
+    // reads its input into its own code
+    check(&[3, 3, 104, 0, 99], &[5], no_patch);
+}
+
+#[test]
+fn dynamic_jumps() {
+    // jumps to a location stored in memory
+    check(
+        &[1005, 9, 10, 104, 1, 99, 0, 0, 0, 7, 104, 2, 99],
+        &[],
+        no_patch,
+    );
+    // the target is only known at run time, from the input
+    check(
+        &[3, 12, 1005, 12, 12, 104, 1, 99, 104, 2, 99, 0, 0],
+        &[8],
+        no_patch,
+    );
+    check(
+        &[1105, 1, 7, 99, 104, 1, 99, 1105, 0, 0, 1105, 1, 4],
+        &[],
+        no_patch,
+    );
+}
+
+#[test]
+fn faulty_programs() {
+    check(&[1, 0, 0, 0, 98], &[], no_patch);
+    check(&[1, -1, 0, 0, 99], &[], no_patch);
+    check(&[1101, 1, 1, 7, 99], &[], no_patch);
+    check(&[11101, 1, 1, 3, 99], &[], no_patch);
+    check(&[30001, 0, 0, 0, 99], &[], no_patch);
+    check(&[109, -5, 204, 0, 99], &[], no_patch);
+    check(&[1102, i64::MAX, 2, 0, 99], &[], no_patch);
+    check(&[3, 0, 3, 0, 99], &[1], no_patch);
+    check(&[1105, 1, -3], &[], no_patch);
+    check(&[1101, 1, 1], &[], no_patch);
+    check(&[1106, 0, 1_000_000], &[], no_patch);
+    check(&[1101, 0, 0, 0], &[], |memory| memory.set_limit(Some(4)));
+    check(&[1101, 1, 1, 100, 99], &[], |memory| {
+        memory.set_limit(Some(50))
+    });
+}
+
+#[test]
+fn instruction_limits() {
+    let day_5 = parse(DAY_5);
+    let day_7 = parse(DAY_7);
+    for limit in &[0, 1, 2, 5, 10, 20, 50, 100, 1000] {
+        check_limited(&day_5, &[5], *limit);
+        check_limited(&day_7, &[4, 0], *limit);
+    }
+    // the limit is reached by a jump and by the terminate instruction
+    check_limited(&[1105, 1, 3, 99], &[], 1);
+    check_limited(&[1101, 1, 1, 0, 99], &[], 1);
+
+    let mut random = Random(7);
+    for _ in 0..200 {
+        let memory = random_program(&mut random);
+        let inputs = (0..5).map(|_| random.next(100)).collect::<Vec<i64>>();
+        check_limited(&memory, &inputs, random.next(30) as u64);
+    }
+}
+
+/// A small deterministic pseudo-random number generator (a linear congruential generator).
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self, bound: i64) -> i64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((self.0 >> 33) % bound as u64) as i64
+    }
+}
+
+/// Generates a program of random instructions over a small data area.
+///
+/// The program only writes to the data area and only jumps forward, so that it always terminates.
+fn random_program(random: &mut Random) -> Vec<i64> {
+    const CODE: i64 = 60;
+    const DATA: i64 = 20;
+    let mode = |random: &mut Random, allow_immediate: bool| match random.next(if allow_immediate {
+        3
+    } else {
+        2
+    }) {
+        0 => 0,
+        1 if allow_immediate => 1,
+        _ => 2,
+    };
+
+    // move the relative base to the data area
+    let mut memory = vec![109, CODE];
+    let mut starts = vec![0];
+    let mut jumps = Vec::new();
+    while (memory.len() as i64) < CODE - 4 {
+        starts.push(memory.len());
+        let (opcode, modes) = match random.next(10) {
+            0 => (
+                1,
+                [mode(random, true), mode(random, true), mode(random, false)],
+            ),
+            1 => (
+                2,
+                [mode(random, true), mode(random, true), mode(random, false)],
+            ),
+            2 => (3, [mode(random, false), 0, 0]),
+            3 => (4, [mode(random, true), 0, 0]),
+            4 => (5, [mode(random, true), 1, 0]),
+            5 => (6, [mode(random, true), 1, 0]),
+            6 => (
+                7,
+                [mode(random, true), mode(random, true), mode(random, false)],
+            ),
+            7 => (
+                8,
+                [mode(random, true), mode(random, true), mode(random, false)],
+            ),
+            8 => (9, [1, 0, 0]),
+            _ => (1, [1, 1, 0]),
+        };
+        memory.push(opcode + 100 * modes[0] + 1000 * modes[1] + 10000 * modes[2]);
+        let (params, dst) = match opcode {
+            3 => (1, Some(0)),
+            4 | 9 => (1, None),
+            5 | 6 => (2, None),
+            _ => (3, Some(2)),
+        };
+        for (idx, mode) in modes.iter().enumerate().take(params) {
+            let value = match (opcode, *mode) {
+                // the jump targets are set once all the instructions are known
+                (5, 1) | (6, 1) if idx == 1 => {
+                    jumps.push(memory.len());
+                    0
+                }
+                // only move the relative base forward, so that it stays in the data area
+                (9, _) => random.next(3),
+                (_, 1) => random.next(21) - 10,
+                (_, 2) => random.next(5),
+                // reads are mostly from the data area, but sometimes from the code
+                _ if dst != Some(idx) && random.next(8) == 0 => random.next(CODE),
+                _ => CODE + random.next(DATA),
+            };
+            memory.push(value);
+        }
+    }
+    memory.resize(CODE as usize, 99);
+    starts.push(memory.len() - 1);
+
+    // jump forward, to the start of a later instruction
+    for jump in jumps {
+        let later = starts
+            .iter()
+            .filter(|start| **start > jump)
+            .collect::<Vec<&usize>>();
+        memory[jump] = *later[random.next(later.len() as i64) as usize] as i64;
+    }
+    memory.extend((0..DATA).map(|_| random.next(7) - 3));
+    memory
+}
+
+#[test]
+fn random_programs() {
+    let mut random = Random(2019);
+    for _ in 0..2000 {
+        let memory = random_program(&mut random);
+        let inputs = (0..5).map(|_| random.next(100)).collect::<Vec<i64>>();
+        check(&memory, &inputs, no_patch);
+    }
+}
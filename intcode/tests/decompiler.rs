@@ -0,0 +1,25 @@
+//! Regression tests of the decompiler on programs that make it fold constants.
+
+use intcode::*;
+
+#[test]
+fn overflowing_constant_add() {
+    let memory = vec![1101, i64::MAX, 1, 5, 4, 5, 99];
+    let pseudocode = decompile(&memory, &[]).to_string();
+    assert!(
+        pseudocode.contains(&format!("{} + 1", i64::MAX)),
+        "{}",
+        pseudocode
+    );
+}
+
+#[test]
+fn overflowing_constant_multiply() {
+    let memory = vec![1102, i64::MAX, 2, 5, 4, 5, 99];
+    let pseudocode = decompile(&memory, &[]).to_string();
+    assert!(
+        pseudocode.contains(&format!("{} * 2", i64::MAX)),
+        "{}",
+        pseudocode
+    );
+}
@@ -0,0 +1,69 @@
+//! Tests of the resource limits across several runs of the same computer.
+
+use intcode::*;
+use std::{collections::VecDeque, thread, time::Duration};
+
+/// Counts down from 1500, executing about 3000 instructions.
+const COUNTDOWN: [i64; 12] = [1101, 0, 1500, 20, 1001, 20, -1, 20, 1005, 20, 4, 99];
+
+/// Jumps to itself forever.
+const LOOP: [i64; 3] = [1105, 1, 0];
+
+/// The time limit of the countdown, far longer than the countdown takes.
+const LIMIT: Duration = Duration::from_millis(500);
+
+fn computer(memory: &[i64], time: Duration) -> IntcodeComputer<VecDeque<i64>, Vec<i64>> {
+    let mut computer = IntcodeComputer::new(memory.to_vec(), VecDeque::new(), Vec::new());
+    computer.set_limits(Limits {
+        time: Some(time),
+        ..Limits::default()
+    });
+    computer
+}
+
+// helper function to check that a result is a time limit error
+fn timed_out<T>(result: Result<T, IntcodeError<i64>>) -> bool {
+    matches!(
+        result,
+        Err(IntcodeError::LimitExceeded {
+            source: LimitExceeded::Time { .. },
+            ..
+        })
+    )
+}
+
+#[test]
+fn time_limit_stops_an_infinite_loop() {
+    let mut computer = computer(&LOOP, Duration::from_millis(10));
+    assert!(timed_out(computer.run()));
+    assert!(timed_out(computer.run()));
+    assert!(timed_out(computer.run_compiled(&compile(&LOOP))));
+    assert!(matches!(
+        computer.run_until_io(),
+        HaltReason::LimitExceeded(LimitExceeded::Time { .. })
+    ));
+}
+
+#[test]
+fn time_limit_restarts_with_each_run() {
+    let mut computer = computer(&COUNTDOWN, LIMIT);
+    // the clock doesn't start when the limits are set
+    thread::sleep(LIMIT);
+    assert!(computer.run().is_ok());
+
+    thread::sleep(LIMIT);
+    assert!(computer.run().is_ok());
+    assert!(computer.run_with_inputs(&[]).is_ok());
+    assert!(computer.run_compiled(&compile(&COUNTDOWN)).is_ok());
+}
+
+#[test]
+fn time_limit_restarts_after_restore() {
+    let mut computer = computer(&COUNTDOWN, LIMIT);
+    let snapshot = computer.snapshot();
+    assert!(matches!(computer.run_until_io(), HaltReason::Halted));
+
+    thread::sleep(LIMIT);
+    computer.restore(&snapshot);
+    assert!(matches!(computer.run_until_io(), HaltReason::Halted));
+}
@@ -0,0 +1,33 @@
+//! Regression tests of the symbolic engine on programs with extreme values.
+
+use intcode::*;
+
+/// Builds a program that compares `2 * x * MAX^2` with its negation, where `x` is at location 100.
+fn extreme_comparison(opcode: i64) -> Vec<i64> {
+    #[rustfmt::skip]
+    let mut memory = vec![
+        1002, 100, i64::MAX, 101, // [101] = x * MAX
+        1002, 101, i64::MAX, 101, // [101] = [101] * MAX
+        1, 101, 101, 102, // [102] = [101] + [101]
+        1002, 102, -1, 103, // [103] = -[102]
+        opcode, 102, 103, 104, // [104] = [102] < [103] (or ==)
+        99,
+    ];
+    memory.resize(105, 0);
+    memory
+}
+
+#[test]
+fn overflowing_comparison() {
+    for &opcode in &[7, 8] {
+        let mut computer = SymbolicComputer::new(&extreme_comparison(opcode));
+        computer.symbolize(100, "x");
+        let paths = computer.explore();
+        assert_eq!(paths.len(), 1);
+        assert!(
+            matches!(paths[0].end, PathEnd::Unsupported { pc: 16, .. }),
+            "{:?}",
+            paths[0].end
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Tests of narrow words: values that don't fit in an `i32` must fail, not wrap around.
+
+use intcode::*;
+use std::collections::VecDeque;
+
+#[test]
+fn relative_base_beyond_the_word() {
+    // moves the relative base to 2^31, then outputs relative location 0
+    let memory: Vec<i32> = vec![109, i32::MAX, 109, 1, 204, 0, 99];
+    let mut computer = IntcodeComputer::new(memory, VecDeque::new(), Vec::new());
+
+    let error = computer.run().unwrap_err();
+    assert!(
+        matches!(error, IntcodeError::Overflow { pc: 4, .. }),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn assembled_value_beyond_the_word() {
+    let error = assemble::<i32>("data 2147483648").unwrap_err();
+    assert_eq!(error.line, 1);
+    assert_eq!(assemble::<i64>("data 2147483648").unwrap(), vec![1 << 31]);
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(i32::checked_from_i64(i64::from(i32::MIN)), Some(i32::MIN));
+    assert_eq!(i32::checked_from_i64(1 << 31), None);
+    assert_eq!(i64::checked_from_i64(1 << 31), Some(1 << 31));
+}
@@ -7,13 +7,17 @@ use std::{
 ///
 /// # Example
 /// ```rust
-/// App::new("Example")
+/// # use clap::{App, Arg};
+/// # use validators::*;
+/// let app = App::new("Example")
 ///     .arg(
 ///         Arg::with_name("input")
 ///             .help("The path to the input file")
 ///             .takes_value(true)
 ///             .validator_os(is_valid_path),
 ///     );
+/// # assert!(app.clone().get_matches_from_safe(vec!["example", "Cargo.toml"]).is_ok());
+/// # assert!(app.get_matches_from_safe(vec!["example", "no/such/file"]).is_err());
 /// ```
 pub fn is_valid_path(path: &OsStr) -> Result<(), OsString> {
     if Path::new(path).exists() {
@@ -27,13 +31,17 @@ pub fn is_valid_path(path: &OsStr) -> Result<(), OsString> {
 ///
 /// # Example
 /// ```rust
-/// App::new("Example")
+/// # use clap::{App, Arg};
+/// # use validators::*;
+/// let app = App::new("Example")
 ///     .arg(
 ///         Arg::with_name("input")
 ///             .help("An i32 number")
 ///             .takes_value(true)
 ///             .validator(is_valid_i32),
 ///     );
+/// # assert!(app.clone().get_matches_from_safe(vec!["example", "42"]).is_ok());
+/// # assert!(app.get_matches_from_safe(vec!["example", "forty-two"]).is_err());
 /// ```
 pub fn is_valid_i32(input: String) -> Result<(), String> {
     match input.parse::<i32>() {
@@ -42,21 +50,48 @@ pub fn is_valid_i32(input: String) -> Result<(), String> {
     }
 }
 
+/// Validates that the given string is a valid `i64`.
+///
+/// # Example
+/// ```rust
+/// # use clap::{App, Arg};
+/// # use validators::*;
+/// let app = App::new("Example")
+///     .arg(
+///         Arg::with_name("input")
+///             .help("An i64 number")
+///             .takes_value(true)
+///             .validator(is_valid_i64),
+///     );
+/// # assert!(app.clone().get_matches_from_safe(vec!["example", "9000000000"]).is_ok());
+/// # assert!(app.get_matches_from_safe(vec!["example", "forty-two"]).is_err());
+/// ```
+pub fn is_valid_i64(input: String) -> Result<(), String> {
+    match input.parse::<i64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(String::from("Could not parse the given input to i64")),
+    }
+}
+
 /// Validates the the given string is a 6-digit `i32`.
 ///
 /// # Example
 /// ```rust
-/// App::new("Example")
+/// # use clap::{App, Arg};
+/// # use validators::*;
+/// let app = App::new("Example")
 ///     .arg(
 ///         Arg::with_name("number")
 ///             .help("A 6-digit number")
 ///             .takes_value(true)
 ///             .validator(is_six_digit)
 ///     );
+/// # assert!(app.clone().get_matches_from_safe(vec!["example", "123456"]).is_ok());
+/// # assert!(app.get_matches_from_safe(vec!["example", "12345"]).is_err());
 /// ```
 pub fn is_six_digit(string: String) -> Result<(), String> {
     let num = string.parse::<i32>().map_err(|err| err.to_string())?;
-    if 100_000 <= num && num <= 999_999 {
+    if (100_000..=999_999).contains(&num) {
         Ok(())
     } else {
         Err(String::from("The number wasn't a 6-digit number"))